@@ -21,7 +21,10 @@ export default [
       window: './src/window.ts',
       cli: './src/cli.ts',
       notification: './src/notification.ts',
-      globalShortcut: './src/globalShortcut.ts'
+      globalShortcut: './src/globalShortcut.ts',
+      store: './src/store.ts',
+      clipboard: './src/clipboard.ts',
+      websocket: './src/websocket.ts'
     },
     treeshake: true,
     perf: true,
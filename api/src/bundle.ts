@@ -10,6 +10,9 @@ import * as tauri from './tauri'
 import * as window from './window'
 import * as notification from './notification'
 import * as globalShortcut from './globalShortcut'
+import * as store from './store'
+import * as clipboard from './clipboard'
+import * as websocket from './websocket'
 
 export {
   cli,
@@ -22,5 +25,8 @@ export {
   tauri,
   window,
   notification,
-  globalShortcut
+  globalShortcut,
+  store,
+  clipboard,
+  websocket
 }
@@ -0,0 +1,33 @@
+import { invoke } from './tauri'
+
+/**
+ * Reads the clipboard content as plain text.
+ * Other formats, e.g. HTML, aren't supported.
+ *
+ * @return promise resolving to the text or null if the clipboard text is empty
+ */
+async function readText(): Promise<string | null> {
+  return invoke<string | null>({
+    __tauriModule: 'Clipboard',
+    message: {
+      cmd: 'readText'
+    }
+  })
+}
+
+/**
+ * Writes plain text to the clipboard, replacing its content.
+ *
+ * @param text the text to write
+ */
+async function writeText(text: string): Promise<void> {
+  return invoke({
+    __tauriModule: 'Clipboard',
+    message: {
+      cmd: 'writeText',
+      text
+    }
+  })
+}
+
+export { readText, writeText }
@@ -1,4 +1,4 @@
-import { invoke } from './tauri'
+import { invoke, transformCallback } from './tauri'
 
 export enum BaseDirectory {
   Audio = 1,
@@ -40,6 +40,39 @@ export interface FsBinaryFileOption {
   contents: ArrayBuffer
 }
 
+export interface FsWatchOptions {
+  dir?: BaseDirectory
+  // whether the subdirectories are watched too
+  recursive?: boolean
+  // time without changes to a path before its last change is sent; defaults to 100
+  debounceMs?: number
+}
+
+export interface FsOpenOptions {
+  dir?: BaseDirectory
+  // opens the file for reading; the default if no access mode is set
+  read?: boolean
+  // opens the file for writing
+  write?: boolean
+  // opens the file for writing, always at its end
+  append?: boolean
+  // truncates the file when opening it for writing
+  truncate?: boolean
+  // creates the file if it doesn't exist
+  create?: boolean
+  // creates the file, failing if it already exists
+  createNew?: boolean
+}
+
+export type SeekFrom = 'start' | 'end' | 'current'
+
+export type WatchEvent =
+  | { type: 'create'; payload: string }
+  | { type: 'modify'; payload: string }
+  | { type: 'remove'; payload: string }
+  | { type: 'rename'; payload: { from: string; to: string } }
+  | { type: 'error'; payload: string }
+
 export interface FileEntry {
   path: string
   // name of the directory/file
@@ -334,6 +367,189 @@ async function renameFile(
   })
 }
 
+/**
+ * @name watch
+ * @description Watches a path for changes; the watcher is stopped when the window is closed
+ * @param {string} path path to the file or directory
+ * @param {FsWatchOptions} options configuration object
+ * @param {BaseDirectory} [options.dir] base directory
+ * @param {boolean} [options.recursive] whether to watch the subdirectories too
+ * @param {number} [options.debounceMs] time without changes to a path before its last change is sent
+ * @param {(event: WatchEvent) => void} handler called with each change
+ * @return {Promise<number>} the watcher ID
+ */
+async function watch(
+  path: string,
+  options: FsWatchOptions,
+  handler: (event: WatchEvent) => void
+): Promise<number> {
+  return invoke<number>({
+    __tauriModule: 'Fs',
+    message: {
+      cmd: 'watch',
+      path,
+      options,
+      onEvent: transformCallback(handler)
+    }
+  })
+}
+
+/**
+ * @name unwatch
+ * @description Stops a watcher
+ * @param {number} id the watcher ID
+ * @return {Promise<void>}
+ */
+async function unwatch(id: number): Promise<void> {
+  return invoke({
+    __tauriModule: 'Fs',
+    message: {
+      cmd: 'unwatch',
+      id
+    }
+  })
+}
+
+class FileHandle {
+  // the file ID
+  id: number
+
+  constructor(id: number) {
+    this.id = id
+  }
+
+  /**
+   * @name read
+   * @description Reads up to `len` bytes, from the offset or from the cursor position
+   * @param {number} len the maximum number of bytes to read
+   * @param {number} [offset] the position to read from
+   * @return {Promise<Uint8Array>} the bytes read, fewer than `len` at the end of the file
+   */
+  async read(len: number, offset?: number): Promise<Uint8Array> {
+    const data = await invoke<string>({
+      __tauriModule: 'Fs',
+      message: {
+        cmd: 'read',
+        id: this.id,
+        len,
+        offset
+      }
+    })
+    return Uint8Array.from(atob(data), (c) => c.charCodeAt(0))
+  }
+
+  /**
+   * @name write
+   * @description Writes the data at the offset or at the cursor position; always at the end in append mode
+   * @param {string | ArrayBuffer} data the text or bytes to write
+   * @param {number} [offset] the position to write at
+   * @return {Promise<number>} the number of bytes written
+   */
+  async write(data: string | ArrayBuffer, offset?: number): Promise<number> {
+    const bytes =
+      typeof data === 'string'
+        ? new TextEncoder().encode(data)
+        : new Uint8Array(data)
+    return invoke<number>({
+      __tauriModule: 'Fs',
+      message: {
+        cmd: 'write',
+        id: this.id,
+        contents: btoa(uint8ArrayToString(bytes)),
+        offset
+      }
+    })
+  }
+
+  /**
+   * @name seek
+   * @description Moves the cursor
+   * @param {number} offset the offset, which can be negative from the end or the current position
+   * @param {SeekFrom} [from] where the offset starts from; defaults to the start of the file
+   * @return {Promise<number>} the new cursor position from the start of the file
+   */
+  async seek(offset: number, from: SeekFrom = 'start'): Promise<number> {
+    return invoke<number>({
+      __tauriModule: 'Fs',
+      message: {
+        cmd: 'seek',
+        id: this.id,
+        from: { from, offset }
+      }
+    })
+  }
+
+  /**
+   * @name truncate
+   * @description Truncates or extends the file to the given length
+   * @param {number} len the new length
+   * @return {Promise<void>}
+   */
+  async truncate(len: number): Promise<void> {
+    return invoke({
+      __tauriModule: 'Fs',
+      message: {
+        cmd: 'truncate',
+        id: this.id,
+        len
+      }
+    })
+  }
+
+  /**
+   * @name sync
+   * @description Flushes the file to the disk
+   * @return {Promise<void>}
+   */
+  async sync(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Fs',
+      message: {
+        cmd: 'sync',
+        id: this.id
+      }
+    })
+  }
+
+  /**
+   * @name close
+   * @description Closes the file
+   * @return {Promise<void>}
+   */
+  async close(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Fs',
+      message: {
+        cmd: 'close',
+        id: this.id
+      }
+    })
+  }
+}
+
+/**
+ * @name open
+ * @description Opens a file to read or write it incrementally; the file is closed when the window is closed
+ * @param {string} path path to the file
+ * @param {FsOpenOptions} [options] configuration object
+ * @param {BaseDirectory} [options.dir] base directory
+ * @return {Promise<FileHandle>}
+ */
+async function open(
+  path: string,
+  options: FsOpenOptions = {}
+): Promise<FileHandle> {
+  const id = await invoke<number>({
+    __tauriModule: 'Fs',
+    message: {
+      cmd: 'open',
+      path,
+      options
+    }
+  })
+  return new FileHandle(id)
+}
+
 export {
   BaseDirectory as Dir,
   readTextFile,
@@ -345,5 +561,9 @@ export {
   removeDir,
   copyFile,
   removeFile,
-  renameFile
+  renameFile,
+  watch,
+  unwatch,
+  FileHandle,
+  open
 }
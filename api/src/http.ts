@@ -1,8 +1,37 @@
-import { invoke } from './tauri'
+import { invoke, transformCallback } from './tauri'
+import { BaseDirectory } from './fs'
+
+export interface ProxyOptions {
+  /** proxy URL for all requests */
+  all?: string
+  /** proxy URL for HTTP requests */
+  http?: string
+  /** proxy URL for HTTPS requests */
+  https?: string
+  username?: string
+  password?: string
+  /** whether the proxy is detected from the environment variables when no proxy URL is set (defaults to true) */
+  system?: boolean
+}
+
+export interface ClientCertificate {
+  /** path to the PKCS #12 archive containing the certificate and its private key */
+  path: string
+  password?: string
+}
 
 export interface ClientOptions {
-  maxRedirections: boolean
-  connectTimeout: number
+  maxRedirections?: number
+  connectTimeout?: number
+  proxy?: ProxyOptions
+  /** name of the cookie jar persisted to the app directory */
+  cookieJar?: string
+  /** paths to additional trusted root certificates, PEM bundles or DER encoded */
+  rootCertificates?: string[]
+  clientCertificate?: ClientCertificate
+  minTlsVersion?: '1.0' | '1.1' | '1.2' | '1.3'
+  /** whether GET responses are cached in the app cache directory, following the HTTP caching headers */
+  cache?: boolean
 }
 
 export enum ResponseType {
@@ -11,7 +40,23 @@ export enum ResponseType {
   Binary = 3
 }
 
-export type Part = 'string' | number[]
+export interface FilePart {
+  /** path of the file to upload; requires the `fs > readBinaryFile` allowlist */
+  file: string
+  /** base directory of the file path */
+  dir?: BaseDirectory
+  mime?: string
+  /** defaults to the file name of the path */
+  fileName?: string
+}
+
+export interface BytesPart {
+  bytes: number[]
+  mime?: string
+  fileName?: string
+}
+
+export type Part = string | FilePart | BytesPart
 
 export class Body {
   type: string
@@ -23,7 +68,7 @@ export class Body {
   }
 
   static form(data: Record<string, Part>): Body {
-    return new Body('Form', data)
+    return new Body('Form', Object.entries(data))
   }
 
   static json(data: Record<any, any>): Body {
@@ -58,6 +103,13 @@ export interface HttpOptions {
   body?: Body
   timeout?: number
   responseType?: ResponseType
+  /** called with the upload progress of a form body */
+  onUploadProgress?: (progress: UploadProgress) => void
+}
+
+export interface UploadProgress {
+  uploaded: number
+  total: number
 }
 
 export type RequestOptions = Omit<HttpOptions, 'method' | 'url'>
@@ -68,6 +120,97 @@ export interface Response<T> {
   status: number
   headers: Record<string, string>
   data: T
+  /** whether the response was served from the client cache */
+  fromCache: boolean
+}
+
+export interface DownloadOptions {
+  headers?: Record<string, any>
+  timeout?: number
+  /** expected SHA-256 checksum of the file, as a hex string */
+  sha256?: string
+  /** whether an existing partial file should be resumed (defaults to true); only files served with an `ETag` or `Last-Modified` validator can be resumed */
+  resume?: boolean
+  /** base directory of the download path */
+  dir?: BaseDirectory
+  onProgress?: (progress: DownloadProgress) => void
+}
+
+export interface DownloadProgress {
+  downloaded: number
+  total?: number
+}
+
+export type DownloadStatus = 'completed' | 'paused' | 'cancelled'
+
+export class ResponseStream {
+  id: number
+  /** the response info, with a `null` data */
+  response: Response<null>
+  /** promise resolving once the whole body was received, or rejecting if reading it failed or the stream was cancelled */
+  finished: Promise<void>
+
+  constructor(id: number, response: Response<null>, finished: Promise<void>) {
+    this.id = id
+    this.response = response
+    this.finished = finished
+  }
+
+  /**
+   * cancels the stream, aborting the request
+   */
+  async cancel(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Http',
+      message: {
+        cmd: 'cancelStream',
+        id: this.id
+      }
+    })
+  }
+}
+
+type StreamEvent =
+  | { type: 'chunk'; payload: string }
+  | { type: 'end' }
+  | { type: 'error'; payload: string }
+  | { type: 'cancelled' }
+
+export class Download {
+  id: number
+  /** promise resolving to the download status once it finishes */
+  finished: Promise<DownloadStatus>
+
+  constructor(id: number, finished: Promise<DownloadStatus>) {
+    this.id = id
+    this.finished = finished
+  }
+
+  /**
+   * pauses the download, keeping the partial file so it can be resumed by downloading it again
+   */
+  async pause(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Http',
+      message: {
+        cmd: 'pauseDownload',
+        id: this.id
+      }
+    })
+  }
+
+  /**
+   * cancels the download, removing the partial file
+   */
+  async cancel(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Http',
+      message: {
+        cmd: 'cancelDownload',
+        id: this.id
+      }
+    })
+  }
 }
 
 export class Client {
@@ -97,14 +240,139 @@ export class Client {
    * @return promise resolving to the response
    */
   async request<T>(options: HttpOptions): Promise<Response<T>> {
+    const { onUploadProgress, ...requestOptions } = options
     return invoke({
       __tauriModule: 'Http',
       message: {
         cmd: 'httpRequest',
         client: this.id,
-        options
+        options: requestOptions,
+        onUploadProgress: onUploadProgress
+          ? transformCallback(onUploadProgress)
+          : undefined
+      }
+    })
+  }
+
+  /**
+   * makes a HTTP request, streaming the response body as it arrives
+   *
+   * the next chunks are only read once the previous ones were handled,
+   * so `onChunk` can return a promise to slow down the stream
+   *
+   * @param options request options; the response type is ignored
+   * @param onChunk called with each chunk of the response body
+   *
+   * @return promise resolving to the response stream once the response headers are received
+   */
+  async stream(
+    options: HttpOptions,
+    onChunk: (chunk: Uint8Array) => void | Promise<void>
+  ): Promise<ResponseStream> {
+    const id = await invoke<number>({
+      __tauriModule: 'Http',
+      message: {
+        cmd: 'createStream'
+      }
+    })
+    const { onUploadProgress, ...requestOptions } = options
+    let resolveFinished: () => void = () => {}
+    let rejectFinished: (error: string) => void = () => {}
+    const finished = new Promise<void>((resolve, reject) => {
+      resolveFinished = resolve
+      rejectFinished = reject
+    })
+    // the chunks are handled one after another, in order
+    let queue: Promise<unknown> = Promise.resolve()
+    const onEvent = transformCallback((event: StreamEvent) => {
+      if (event.type === 'chunk') {
+        const chunk = Uint8Array.from(atob(event.payload), (c) =>
+          c.charCodeAt(0)
+        )
+        queue = queue
+          .then(async () => onChunk(chunk))
+          .catch(console.error)
+          .then(async () =>
+            invoke({
+              __tauriModule: 'Http',
+              message: {
+                cmd: 'ackStreamChunk',
+                id
+              }
+            })
+          )
+          .catch(console.error)
+      } else {
+        Reflect.deleteProperty(window, onEvent)
+        queue = queue.then(() => {
+          if (event.type === 'end') {
+            resolveFinished()
+          } else if (event.type === 'error') {
+            rejectFinished(event.payload)
+          } else {
+            rejectFinished('the stream was cancelled')
+          }
+        })
+      }
+    })
+    const response = await invoke<Response<null>>({
+      __tauriModule: 'Http',
+      message: {
+        cmd: 'httpRequest',
+        client: this.id,
+        options: requestOptions,
+        onUploadProgress: onUploadProgress
+          ? transformCallback(onUploadProgress)
+          : undefined,
+        stream: {
+          id,
+          onEvent
+        }
+      }
+    }).catch((error) => {
+      // no stream event is sent if the request fails
+      Reflect.deleteProperty(window, onEvent)
+      throw error
+    })
+    return new ResponseStream(id, response, finished)
+  }
+
+  /**
+   * downloads a file to the given path
+   *
+   * @param url file URL
+   * @param path destination path
+   * @param options download options
+   *
+   * @return promise resolving to the download, which can be paused or cancelled
+   */
+  async download(
+    url: string,
+    path: string,
+    options?: DownloadOptions
+  ): Promise<Download> {
+    const id = await invoke<number>({
+      __tauriModule: 'Http',
+      message: {
+        cmd: 'createDownload'
+      }
+    })
+    const { onProgress, ...downloadOptions } = options ?? {}
+    const finished = invoke<DownloadStatus>({
+      __tauriModule: 'Http',
+      message: {
+        cmd: 'download',
+        id,
+        client: this.id,
+        options: {
+          url,
+          path,
+          ...downloadOptions
+        },
+        onProgress: onProgress ? transformCallback(onProgress) : undefined
       }
     })
+    return new Download(id, finished)
   }
 
   /**
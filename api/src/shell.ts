@@ -1,43 +1,244 @@
-import { invoke } from './tauri'
+import { invoke, transformCallback } from './tauri'
+
+export interface SpawnOptions {
+  /** working directory of the process, only allowed if the scope command sets `cwd` */
+  cwd?: string
+  /** environment variables added to the ones of the app, only allowed if they're listed in the scope command `env` */
+  env?: Record<string, string>
+}
+
+export interface TerminatedPayload {
+  /** exit code, if the process wasn't terminated by a signal */
+  code: number | null
+  /** signal that terminated the process, on Unix */
+  signal: number | null
+}
+
+export interface ChildProcess extends TerminatedPayload {
+  stdout: string
+  stderr: string
+}
+
+type CommandEvent =
+  | { id: number; event: 'Stdout'; payload: string }
+  | { id: number; event: 'Stderr'; payload: string }
+  | { id: number; event: 'Error'; payload: string }
+  | { id: number; event: 'Terminated'; payload: TerminatedPayload }
+
+interface CommandListeners {
+  /** called with each line written to stdout */
+  stdout: Array<(line: string) => void>
+  /** called with each line written to stderr */
+  stderr: Array<(line: string) => void>
+  /** called if reading the output or waiting for the process failed */
+  error: Array<(error: string) => void>
+  /** called once the process terminated, after its whole output */
+  close: Array<(payload: TerminatedPayload) => void>
+}
+
+export class Child {
+  /** the child process ID */
+  pid: number
+
+  constructor(pid: number) {
+    this.pid = pid
+  }
+
+  /**
+   * writes to the process stdin
+   *
+   * @param data the text or bytes to write
+   */
+  async write(data: string | number[] | Uint8Array): Promise<void> {
+    return invoke({
+      __tauriModule: 'Shell',
+      message: {
+        cmd: 'stdinWrite',
+        id: this.pid,
+        buffer: typeof data === 'string' ? data : Array.from(data)
+      }
+    })
+  }
+
+  /**
+   * kills the process; the close event is still emitted
+   */
+  async kill(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Shell',
+      message: {
+        cmd: 'killChild',
+        id: this.pid
+      }
+    })
+  }
+}
+
+export class Command {
+  program: string
+  args: string[]
+  options: SpawnOptions
+  private readonly listeners: CommandListeners = {
+    stdout: [],
+    stderr: [],
+    error: [],
+    close: []
+  }
+
+  /**
+   * @param program the name of the command in the `tauri > allowlist > shell > scope` config
+   * @param [args] program args, which must be allowed by the scope command
+   * @param [options] spawn options
+   */
+  constructor(
+    program: string,
+    args: string | string[] = [],
+    options: SpawnOptions = {}
+  ) {
+    this.program = program
+    this.args = typeof args === 'string' ? [args] : args
+    this.options = options
+  }
+
+  /**
+   * listens to the process events
+   *
+   * @param event the event name
+   * @param handler called with the event payload
+   */
+  on<K extends keyof CommandListeners>(
+    event: K,
+    handler: CommandListeners[K][number]
+  ): this {
+    ;(this.listeners[event] as Array<typeof handler>).push(handler)
+    return this
+  }
+
+  /**
+   * spawns the process
+   *
+   * @return promise resolving to the child process
+   */
+  async spawn(): Promise<Child> {
+    const onEvent = transformCallback((event: CommandEvent) => {
+      switch (event.event) {
+        case 'Stdout':
+          this.listeners.stdout.forEach((handler) => handler(event.payload))
+          break
+        case 'Stderr':
+          this.listeners.stderr.forEach((handler) => handler(event.payload))
+          break
+        case 'Error':
+          this.listeners.error.forEach((handler) => handler(event.payload))
+          break
+        case 'Terminated':
+          Reflect.deleteProperty(window, onEvent)
+          this.listeners.close.forEach((handler) => handler(event.payload))
+          break
+      }
+    })
+    const pid = await invoke<number>({
+      __tauriModule: 'Shell',
+      message: {
+        cmd: 'execute',
+        command: this.program,
+        args: Object.freeze([...this.args]),
+        options: this.options,
+        onEvent
+      }
+    })
+    return new Child(pid)
+  }
+
+  /**
+   * spawns the process and waits for it to terminate
+   *
+   * @return promise resolving to the exit status and the output
+   */
+  async execute(): Promise<ChildProcess> {
+    return new Promise((resolve, reject) => {
+      const stdout: string[] = []
+      const stderr: string[] = []
+      this.on('stdout', (line) => stdout.push(line))
+        .on('stderr', (line) => stderr.push(line))
+        .on('error', reject)
+        .on('close', (payload) =>
+          resolve({
+            ...payload,
+            stdout: stdout.join('\n'),
+            stderr: stderr.join('\n')
+          })
+        )
+      this.spawn().catch(reject)
+    })
+  }
+}
 
 /**
- * spawns a process
+ * spawns a process and waits for it to terminate
  *
- * @param command the name of the cmd to execute e.g. 'mkdir' or 'node'
- * @param [args] command args
- * @return promise resolving to the stdout text
+ * @param command the name of the command in the `tauri > allowlist > shell > scope` config
+ * @param [args] command args, which must be allowed by the scope command
+ * @return promise resolving to the stdout text, or rejecting with the stderr text if the process failed
  */
 async function execute(
   command: string,
   args?: string | string[]
 ): Promise<string> {
-  if (typeof args === 'object') {
-    Object.freeze(args)
+  const output = await new Command(command, args).execute()
+  if (output.code !== 0) {
+    throw output.stderr
   }
+  return output.stdout
+}
 
-  return invoke<string>({
+/**
+ * a program used to open files and URLs
+ */
+export type Program =
+  | 'open'
+  | 'explorer'
+  | 'xdg-open'
+  | 'gio'
+  | 'gnome-open'
+  | 'kde-open'
+  | 'wslview'
+  | 'firefox'
+  | 'google-chrome'
+  | 'chromium'
+  | 'safari'
+
+/**
+ * opens a path or URL with the default application for its type, or with the given program
+ *
+ * @param path the path or URL to open, whose scheme must be allowed by the `tauri > allowlist > shell > openSchemes` config
+ * @param [openWith] the program to open it with
+ */
+async function open(path: string, openWith?: Program): Promise<void> {
+  return invoke({
     __tauriModule: 'Shell',
     message: {
-      cmd: 'execute',
-      command,
-      args: typeof args === 'string' ? [args] : args
+      cmd: 'open',
+      path,
+      with: openWith
     }
   })
 }
 
 /**
- * opens an URL on the user default browser
+ * shows a file or directory in the file manager
+ * the path is only revealed if the `file` scheme is listed in the `tauri > allowlist > shell > openSchemes` config
  *
- * @param url the URL to open
+ * @param path the path to reveal
  */
-async function open(url: string): Promise<void> {
+async function reveal(path: string): Promise<void> {
   return invoke({
     __tauriModule: 'Shell',
     message: {
-      cmd: 'open',
-      uri: url
+      cmd: 'reveal',
+      path
     }
   })
 }
 
-export { execute, open }
+export { execute, open, reveal }
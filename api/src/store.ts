@@ -0,0 +1,117 @@
+import { invoke } from './tauri'
+import { EventCallback, listen } from './helpers/event'
+
+export interface StoreChangedPayload {
+  store: string
+  key: string | null
+  value: unknown | null
+}
+
+/**
+ * A persistent key-value store saved on the app directory.
+ */
+class Store {
+  name: string
+
+  /**
+   * @param name the store name, used as file name. It can't contain path separators or start with a '.'
+   */
+  constructor(name: string) {
+    this.name = name
+  }
+
+  /**
+   * Gets the value associated with the given key.
+   *
+   * @param key the entry key
+   * @return promise resolving to the value or null if the key isn't set
+   */
+  async get<T>(key: string): Promise<T | null> {
+    return invoke<T | null>({
+      __tauriModule: 'Store',
+      message: {
+        cmd: 'get',
+        store: this.name,
+        key
+      }
+    })
+  }
+
+  /**
+   * Sets the value associated with the given key.
+   *
+   * @param key the entry key
+   * @param value the entry value, must be JSON serializable
+   */
+  async set(key: string, value: unknown): Promise<void> {
+    return invoke({
+      __tauriModule: 'Store',
+      message: {
+        cmd: 'set',
+        store: this.name,
+        key,
+        value
+      }
+    })
+  }
+
+  /**
+   * Deletes the given key from the store.
+   *
+   * @param key the entry key
+   * @return promise resolving to whether the key was present or not
+   */
+  async delete(key: string): Promise<boolean> {
+    return invoke<boolean>({
+      __tauriModule: 'Store',
+      message: {
+        cmd: 'delete',
+        store: this.name,
+        key
+      }
+    })
+  }
+
+  /**
+   * Gets all store entries.
+   */
+  async entries(): Promise<Record<string, unknown>> {
+    return invoke<Record<string, unknown>>({
+      __tauriModule: 'Store',
+      message: {
+        cmd: 'entries',
+        store: this.name
+      }
+    })
+  }
+
+  /**
+   * Removes all entries from the store.
+   */
+  async clear(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Store',
+      message: {
+        cmd: 'clear',
+        store: this.name
+      }
+    })
+  }
+
+  /**
+   * Listen to changes on this store, made by any window or by the Rust core.
+   *
+   * @param handler the event handler callback
+   */
+  async onChange(
+    handler: EventCallback<StoreChangedPayload>
+  ): Promise<void> {
+    return listen<StoreChangedPayload>('tauri://store-changed', (event) => {
+      if (event.payload.store === this.name) {
+        handler(event)
+      }
+    })
+  }
+}
+
+export { Store }
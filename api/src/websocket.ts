@@ -0,0 +1,112 @@
+import { invoke, transformCallback } from './tauri'
+
+export type Message =
+  | { type: 'text'; data: string }
+  | { type: 'binary'; data: number[] }
+
+export interface CloseFrame {
+  /** close status code, e.g. 1000 for a normal closure */
+  code: number
+  reason?: string
+}
+
+export type WebSocketEvent =
+  | { type: 'message'; data: Message }
+  | { type: 'close'; data: CloseFrame | null }
+  /** the connection failed; no more events are sent */
+  | { type: 'error'; data: string }
+
+export interface ConnectOptions {
+  /** handshake request headers */
+  headers?: Record<string, string>
+}
+
+export class WebSocket {
+  id: number
+  private readonly listeners: Array<(event: WebSocketEvent) => void>
+
+  constructor(
+    id: number,
+    listeners: Array<(event: WebSocketEvent) => void>
+  ) {
+    this.id = id
+    this.listeners = listeners
+  }
+
+  /**
+   * connects to a WebSocket server, which must be allowed by the HTTP scope
+   *
+   * @param url WebSocket URL, using the `ws` or `wss` scheme
+   * @param options connection options
+   *
+   * @return promise resolving to the connection once the handshake finishes
+   */
+  static async connect(
+    url: string,
+    options?: ConnectOptions
+  ): Promise<WebSocket> {
+    const listeners: Array<(event: WebSocketEvent) => void> = []
+    const onEvent = transformCallback((event: WebSocketEvent) => {
+      if (event.type !== 'message') {
+        Reflect.deleteProperty(window, onEvent)
+      }
+      listeners.forEach((listener) => listener(event))
+    })
+    const id = await invoke<number>({
+      __tauriModule: 'Websocket',
+      message: {
+        cmd: 'connect',
+        options: {
+          url,
+          headers: options?.headers
+        },
+        onEvent
+      }
+    })
+    return new WebSocket(id, listeners)
+  }
+
+  /**
+   * listens to the connection messages, close and error events
+   *
+   * @param listener called with each event
+   */
+  addListener(listener: (event: WebSocketEvent) => void): void {
+    this.listeners.push(listener)
+  }
+
+  /**
+   * sends a text message, or a binary message if given bytes
+   *
+   * @param message the message
+   */
+  async send(message: string | number[] | Uint8Array): Promise<void> {
+    return invoke({
+      __tauriModule: 'Websocket',
+      message: {
+        cmd: 'send',
+        id: this.id,
+        message:
+          typeof message === 'string'
+            ? { type: 'text', data: message }
+            : { type: 'binary', data: Array.from(message) }
+      }
+    })
+  }
+
+  /**
+   * starts the close handshake; a close event is sent once the server replies
+   *
+   * @param frame close status code and reason
+   */
+  async close(frame?: CloseFrame): Promise<void> {
+    return invoke({
+      __tauriModule: 'Websocket',
+      message: {
+        cmd: 'close',
+        id: this.id,
+        frame
+      }
+    })
+  }
+}
@@ -414,6 +414,58 @@ class WindowManager {
       }
     })
   }
+
+  /**
+   * Navigates the webview to the given URL.
+   * The URL must match the `tauri.allowlist.window.navigationScope` patterns and be allowed by the webview navigation handler, if any.
+   *
+   * @param url the URL to load
+   */
+  async navigate(url: string): Promise<void> {
+    return invoke({
+      __tauriModule: 'Window',
+      message: {
+        cmd: 'navigate',
+        url
+      }
+    })
+  }
+
+  /**
+   * Reloads the current page.
+   */
+  async reload(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Window',
+      message: {
+        cmd: 'reload'
+      }
+    })
+  }
+
+  /**
+   * Navigates back in the webview history.
+   */
+  async goBack(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Window',
+      message: {
+        cmd: 'goBack'
+      }
+    })
+  }
+
+  /**
+   * Navigates forward in the webview history.
+   */
+  async goForward(): Promise<void> {
+    return invoke({
+      __tauriModule: 'Window',
+      message: {
+        cmd: 'goForward'
+      }
+    })
+  }
 }
 
 const manager = new WindowManager()
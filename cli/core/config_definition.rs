@@ -284,6 +284,10 @@ struct FsAllowlistConfig {
   rename_file: bool,
   #[serde(default)]
   path: bool,
+  #[serde(default)]
+  watch: bool,
+  #[serde(default)]
+  open: bool,
 }
 
 impl Allowlist for FsAllowlistConfig {
@@ -303,6 +307,8 @@ impl Allowlist for FsAllowlistConfig {
       check_feature!(self, features, remove_file, "fs-remove-file");
       check_feature!(self, features, rename_file, "fs-rename-file");
       check_feature!(self, features, path, "fs-path");
+      check_feature!(self, features, watch, "fs-watch");
+      check_feature!(self, features, open, "fs-open");
       features
     }
   }
@@ -315,6 +321,14 @@ struct WindowAllowlistConfig {
   all: bool,
   #[serde(default)]
   create: bool,
+  #[serde(default)]
+  navigate: bool,
+  /// The URL patterns the window navigate API can load, e.g. `https://*.tauri.studio/`.
+  /// A pattern is made of a scheme, a host glob where `*` matches any sequence of characters and a path prefix.
+  ///
+  /// Navigating to any other URL is rejected.
+  #[serde(default)]
+  navigation_scope: Vec<String>,
 }
 
 impl Allowlist for WindowAllowlistConfig {
@@ -324,11 +338,54 @@ impl Allowlist for WindowAllowlistConfig {
     } else {
       let mut features = Vec::new();
       check_feature!(self, features, create, "window-create");
+      check_feature!(self, features, navigate, "window-navigate");
       features
     }
   }
 }
 
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+enum ShellAllowedArg {
+  /// An argument that must be equal to the string.
+  Fixed(String),
+  /// An argument that must match the regular expression.
+  Var {
+    /// The regular expression. It must match the whole argument.
+    validator: String,
+  },
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+enum ShellAllowedArgs {
+  /// `true` allows any arguments and `false` allows none.
+  Flag(bool),
+  /// The allowed arguments, one per position.
+  List(Vec<ShellAllowedArg>),
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ShellAllowedCommand {
+  /// The name used by the shell execute API to refer to the command.
+  name: String,
+  /// The program path, or the external binary name if the command is a sidecar.
+  cmd: String,
+  /// Whether the command is an external binary bundled with the app.
+  #[serde(default)]
+  sidecar: bool,
+  /// The allowed arguments. Defaults to no arguments.
+  args: Option<ShellAllowedArgs>,
+  /// Whether the shell execute API can set the working directory of the command.
+  #[serde(default)]
+  cwd: bool,
+  /// The environment variables the shell execute API can set on the command.
+  env: Option<Vec<String>>,
+}
+
+#[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct ShellAllowlistConfig {
@@ -338,6 +395,12 @@ struct ShellAllowlistConfig {
   execute: bool,
   #[serde(default)]
   open: bool,
+  /// The commands the shell execute API can spawn, referred to by their name.
+  #[serde(default)]
+  scope: Vec<ShellAllowedCommand>,
+  /// The URL schemes the shell open API can open. Paths are allowed with the `file` scheme.
+  /// Defaults to `https` and `mailto`.
+  open_schemes: Option<Vec<String>>,
 }
 
 impl Allowlist for ShellAllowlistConfig {
@@ -377,6 +440,7 @@ impl Allowlist for DialogAllowlistConfig {
   }
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct HttpAllowlistConfig {
@@ -384,6 +448,17 @@ struct HttpAllowlistConfig {
   all: bool,
   #[serde(default)]
   request: bool,
+  #[serde(default)]
+  download: bool,
+  #[serde(default)]
+  websocket: bool,
+  /// The URL patterns the HTTP and WebSocket APIs can access, e.g. `https://*.tauri.studio/api/` or `wss://*.tauri.studio`.
+  /// A pattern is made of a scheme, a host glob where `*` matches any sequence of characters and a path prefix.
+  ///
+  /// If not set, every URL is allowed.
+  scope: Option<Vec<String>>,
+  /// The maximum number of live HTTP clients a window can create. Defaults to 10.
+  max_clients_per_window: Option<usize>,
 }
 
 impl Allowlist for HttpAllowlistConfig {
@@ -393,6 +468,8 @@ impl Allowlist for HttpAllowlistConfig {
     } else {
       let mut features = Vec::new();
       check_feature!(self, features, request, "http-request");
+      check_feature!(self, features, download, "http-download");
+      check_feature!(self, features, websocket, "http-websocket");
       features
     }
   }
@@ -432,6 +509,47 @@ impl Allowlist for GlobalShortcutAllowlistConfig {
   }
 }
 
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct StoreAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+}
+
+impl Allowlist for StoreAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["store-all"]
+    } else {
+      vec![]
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ClipboardAllowlistConfig {
+  #[serde(default)]
+  all: bool,
+  #[serde(default)]
+  read: bool,
+  #[serde(default)]
+  write: bool,
+}
+
+impl Allowlist for ClipboardAllowlistConfig {
+  fn to_features(&self) -> Vec<&str> {
+    if self.all {
+      vec!["clipboard-all"]
+    } else {
+      let mut features = Vec::new();
+      check_feature!(self, features, read, "clipboard-read");
+      check_feature!(self, features, write, "clipboard-write");
+      features
+    }
+  }
+}
+
 #[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct AllowlistConfig {
@@ -451,6 +569,10 @@ struct AllowlistConfig {
   notification: NotificationAllowlistConfig,
   #[serde(default)]
   global_shortcut: GlobalShortcutAllowlistConfig,
+  #[serde(default)]
+  store: StoreAllowlistConfig,
+  #[serde(default)]
+  clipboard: ClipboardAllowlistConfig,
 }
 
 impl Allowlist for AllowlistConfig {
@@ -466,11 +588,25 @@ impl Allowlist for AllowlistConfig {
       features.extend(self.http.to_features());
       features.extend(self.notification.to_features());
       features.extend(self.global_shortcut.to_features());
+      features.extend(self.store.to_features());
+      features.extend(self.clipboard.to_features());
       features
     }
   }
 }
 
+/// The async runtime configuration object.
+/// Only used when Tauri creates its own runtime.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AsyncRuntimeConfig {
+  /// The number of worker threads. Defaults to the number of cores available to the system.
+  pub worker_threads: Option<usize>,
+  /// The limit for additional threads spawned by the runtime to run blocking tasks.
+  pub max_blocking_threads: Option<usize>,
+}
+
 /// The Tauri configuration object.
 #[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -488,6 +624,9 @@ pub struct TauriConfig {
   #[serde(default)]
   allowlist: AllowlistConfig,
   pub security: Option<SecurityConfig>,
+  /// The async runtime configuration.
+  #[serde(default)]
+  pub async_runtime: AsyncRuntimeConfig,
 }
 
 impl TauriConfig {
@@ -0,0 +1,72 @@
+use ::clipboard::{ClipboardContext, ClipboardProvider};
+use once_cell::sync::Lazy;
+
+use std::{
+  error::Error,
+  sync::{
+    mpsc::{channel, Sender},
+    Mutex,
+  },
+  thread,
+};
+
+type Operation = Box<dyn FnOnce(&mut Option<ClipboardContext>) + Send>;
+
+/// Runs the operation on the system clipboard.
+///
+/// The clipboard context lives on its own thread since it can't be shared between threads on macOS,
+/// and it's kept alive since on Linux the copied content is served by it.
+fn with_clipboard<T, F>(operation: F) -> crate::Result<T>
+where
+  T: Send + 'static,
+  F: FnOnce(&mut ClipboardContext) -> Result<T, Box<dyn Error>> + Send + 'static,
+{
+  static OPERATIONS: Lazy<Mutex<Sender<Operation>>> = Lazy::new(|| {
+    let (tx, rx) = channel::<Operation>();
+    thread::spawn(move || {
+      let mut clipboard = None;
+      for operation in rx {
+        operation(&mut clipboard);
+      }
+    });
+    Mutex::new(tx)
+  });
+
+  let (tx, rx) = channel();
+  let operation: Operation = Box::new(move |clipboard| {
+    let result = match clipboard {
+      Some(clipboard) => operation(clipboard),
+      None => {
+        ClipboardContext::new().and_then(|context| operation(clipboard.get_or_insert(context)))
+      }
+    };
+    // the error isn't `Send`
+    let _ = tx.send(result.map_err(|e| e.to_string()));
+  });
+  OPERATIONS
+    .lock()
+    .expect("Failed to lock clipboard: with_clipboard()")
+    .send(operation)
+    .map_err(|_| crate::Error::Clipboard("the clipboard thread stopped".to_string()))?;
+  rx.recv()
+    .map_err(|_| crate::Error::Clipboard("the clipboard thread stopped".to_string()))?
+    .map_err(crate::Error::Clipboard)
+}
+
+/// Reads the clipboard content as plain text.
+/// Returns `None` if the clipboard text is empty.
+pub fn read_text() -> crate::Result<Option<String>> {
+  with_clipboard(|clipboard| clipboard.get_contents()).map(|text| {
+    if text.is_empty() {
+      None
+    } else {
+      Some(text)
+    }
+  })
+}
+
+/// Writes plain text to the clipboard, replacing its content.
+pub fn write_text(text: impl Into<String>) -> crate::Result<()> {
+  let text = text.into();
+  with_clipboard(|clipboard| clipboard.set_contents(text))
+}
@@ -1,10 +1,29 @@
-use std::process::{Child, Command, Stdio};
+use futures_util::future::{self, Either};
+use serde::Serialize;
+use tokio::{
+  io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader},
+  process::ChildStdin,
+  sync::{
+    mpsc::{self, Receiver, Sender},
+    oneshot, Mutex as AsyncMutex,
+  },
+};
+
+use std::{
+  collections::HashMap,
+  path::PathBuf,
+  process::{Child, Command, ExitStatus, Stdio},
+  sync::{Arc, Mutex},
+};
 
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
 #[cfg(windows)]
-const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+pub(crate) const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// The number of child process events buffered before the output readers wait for the receiver.
+const EVENT_BUFFER: usize = 32;
 
 use tauri_utils::platform;
 
@@ -101,6 +120,248 @@ pub fn binary_command(binary_name: String) -> crate::Result<String> {
   ))
 }
 
+/// The exit status of a child process.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TerminatedPayload {
+  /// The exit code, if the process wasn't terminated by a signal.
+  pub code: Option<i32>,
+  /// The signal that terminated the process, on Unix.
+  pub signal: Option<i32>,
+}
+
+impl From<ExitStatus> for TerminatedPayload {
+  fn from(status: ExitStatus) -> Self {
+    #[cfg(unix)]
+    let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+    #[cfg(not(unix))]
+    let signal = None;
+    Self {
+      code: status.code(),
+      signal,
+    }
+  }
+}
+
+/// An event of a spawned child process.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", content = "payload")]
+pub enum CommandEvent {
+  /// A line written to the standard output, without its line ending.
+  Stdout(String),
+  /// A line written to the standard error, without its line ending.
+  Stderr(String),
+  /// Reading the output or waiting for the process failed.
+  Error(String),
+  /// The process terminated. This is always the last event.
+  Terminated(TerminatedPayload),
+}
+
+/// A child process builder.
+///
+/// # Examples
+/// ```no_run
+/// use tauri_api::command::{CommandBuilder, CommandEvent};
+///
+/// # async fn run() -> tauri_api::Result<()> {
+/// let (mut events, child) = CommandBuilder::new("node")
+///   .args(vec!["--interactive".to_string()])
+///   .spawn()?;
+/// child.write(b"console.log('tauri')\n").await?;
+/// child.close_stdin().await;
+/// while let Some(event) = events.recv().await {
+///   if let CommandEvent::Stdout(line) = event {
+///     println!("got: {}", line);
+///   }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommandBuilder {
+  program: String,
+  args: Vec<String>,
+  cwd: Option<PathBuf>,
+  env: HashMap<String, String>,
+}
+
+impl CommandBuilder {
+  /// Creates a builder for the given program, resolved with the `PATH` if it isn't a path.
+  pub fn new(program: impl Into<String>) -> Self {
+    Self {
+      program: program.into(),
+      args: Vec::new(),
+      cwd: None,
+      env: HashMap::new(),
+    }
+  }
+
+  /// Sets the process arguments.
+  pub fn args(mut self, args: Vec<String>) -> Self {
+    self.args = args;
+    self
+  }
+
+  /// Sets the process working directory.
+  pub fn current_dir(mut self, cwd: impl Into<PathBuf>) -> Self {
+    self.cwd = Some(cwd.into());
+    self
+  }
+
+  /// Adds environment variables to the ones inherited from the current process.
+  pub fn envs(mut self, env: HashMap<String, String>) -> Self {
+    self.env.extend(env);
+    self
+  }
+
+  /// Spawns the process, returning its events and a handle to write to its stdin or kill it.
+  ///
+  /// The output is only read while the events are received.
+  /// The process is killed if the runtime is shut down before it terminates.
+  /// This must be called from a Tokio runtime.
+  pub fn spawn(self) -> crate::Result<(Receiver<CommandEvent>, CommandChild)> {
+    let mut command = tokio::process::Command::new(&self.program);
+    command
+      .kill_on_drop(true)
+      .args(&self.args)
+      .envs(&self.env)
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped());
+    if let Some(cwd) = &self.cwd {
+      command.current_dir(cwd);
+    }
+    #[cfg(windows)]
+    command.creation_flags(CREATE_NO_WINDOW);
+
+    let mut child = command.spawn()?;
+    let pid = child.id().unwrap_or_default();
+    let stdin = child.stdin.take();
+    let (tx, rx) = mpsc::channel(EVENT_BUFFER);
+    let stdout_reader = child.stdout.take().map(|stdout| {
+      tokio::spawn(read_lines(
+        BufReader::new(stdout),
+        tx.clone(),
+        CommandEvent::Stdout,
+      ))
+    });
+    let stderr_reader = child.stderr.take().map(|stderr| {
+      tokio::spawn(read_lines(
+        BufReader::new(stderr),
+        tx.clone(),
+        CommandEvent::Stderr,
+      ))
+    });
+
+    let (kill_tx, kill_rx) = oneshot::channel();
+    tokio::spawn(async move {
+      let status = {
+        let wait = Box::pin(child.wait());
+        match future::select(wait, kill_rx).await {
+          Either::Left((status, _)) => Some(status),
+          Either::Right((Ok(()), _)) => None,
+          // the handle was dropped without killing the process
+          Either::Right((Err(_), wait)) => Some(wait.await),
+        }
+      };
+      let status = match status {
+        Some(status) => status,
+        None => match child.kill().await {
+          Ok(()) => child.wait().await,
+          Err(e) => Err(e),
+        },
+      };
+
+      // the termination is sent after the whole output
+      for reader in stdout_reader.into_iter().chain(stderr_reader) {
+        let _ = reader.await;
+      }
+      let event = match status {
+        Ok(status) => CommandEvent::Terminated(status.into()),
+        Err(e) => CommandEvent::Error(e.to_string()),
+      };
+      let _ = tx.send(event).await;
+    });
+
+    Ok((
+      rx,
+      CommandChild {
+        pid,
+        stdin: Arc::new(AsyncMutex::new(stdin)),
+        kill_tx: Arc::new(Mutex::new(Some(kill_tx))),
+      },
+    ))
+  }
+}
+
+/// Sends the lines of the output to the event channel.
+async fn read_lines<R: AsyncBufRead + Unpin, F: Fn(String) -> CommandEvent>(
+  mut reader: R,
+  tx: Sender<CommandEvent>,
+  wrap: F,
+) {
+  let mut line = Vec::new();
+  loop {
+    line.clear();
+    let event = match reader.read_until(b'\n', &mut line).await {
+      Ok(0) => break,
+      Ok(_) => {
+        while line.last() == Some(&b'\n') || line.last() == Some(&b'\r') {
+          line.pop();
+        }
+        wrap(String::from_utf8_lossy(&line).into_owned())
+      }
+      Err(e) => CommandEvent::Error(e.to_string()),
+    };
+    let error = matches!(event, CommandEvent::Error(_));
+    if tx.send(event).await.is_err() || error {
+      break;
+    }
+  }
+}
+
+/// A handle to a spawned child process. Its clones refer to the same process.
+#[derive(Debug, Clone)]
+pub struct CommandChild {
+  pid: u32,
+  stdin: Arc<AsyncMutex<Option<ChildStdin>>>,
+  kill_tx: Arc<Mutex<Option<oneshot::Sender<()>>>>,
+}
+
+impl CommandChild {
+  /// The process identifier.
+  pub fn pid(&self) -> u32 {
+    self.pid
+  }
+
+  /// Writes the data to the process stdin.
+  pub async fn write(&self, data: &[u8]) -> crate::Result<()> {
+    let mut stdin = self.stdin.lock().await;
+    let stdin = stdin
+      .as_mut()
+      .ok_or_else(|| crate::Error::Command("the process stdin is closed".to_string()))?;
+    stdin.write_all(data).await?;
+    stdin.flush().await?;
+    Ok(())
+  }
+
+  /// Closes the process stdin, so it reads the end of file.
+  pub async fn close_stdin(&self) {
+    self.stdin.lock().await.take();
+  }
+
+  /// Kills the process. Its termination is still sent as an event.
+  pub fn kill(&self) {
+    let kill_tx = self
+      .kill_tx
+      .lock()
+      .expect("Failed to lock kill sender: kill()")
+      .take();
+    if let Some(kill_tx) = kill_tx {
+      let _ = kill_tx.send(());
+    }
+  }
+}
+
 // tests for the commands functions.
 #[cfg(test)]
 mod test {
@@ -148,6 +409,67 @@ mod test {
     }
   }
 
+  #[cfg(not(windows))]
+  #[tokio::test]
+  // test the spawned process events and stdin
+  async fn check_spawn_events() {
+    let (mut events, child) = CommandBuilder::new("sh")
+      .args(vec![
+        "-c".to_string(),
+        "echo $GREETING; echo oops >&2; read name; echo $name; exit 3".to_string(),
+      ])
+      .envs(
+        vec![("GREETING".to_string(), "hello".to_string())]
+          .into_iter()
+          .collect(),
+      )
+      .spawn()
+      .unwrap();
+    child.write(b"tauri\n").await.unwrap();
+
+    let mut received = Vec::new();
+    while let Some(event) = events.recv().await {
+      received.push(event);
+    }
+    assert_eq!(
+      received.last(),
+      Some(&CommandEvent::Terminated(TerminatedPayload {
+        code: Some(3),
+        signal: None
+      }))
+    );
+    let stdout: Vec<&CommandEvent> = received
+      .iter()
+      .filter(|event| matches!(event, CommandEvent::Stdout(_)))
+      .collect();
+    assert_eq!(
+      stdout,
+      vec![
+        &CommandEvent::Stdout("hello".to_string()),
+        &CommandEvent::Stdout("tauri".to_string())
+      ]
+    );
+    assert!(received.contains(&CommandEvent::Stderr("oops".to_string())));
+  }
+
+  #[cfg(not(windows))]
+  #[tokio::test]
+  // test killing a spawned process
+  async fn check_kill() {
+    let (mut events, child) = CommandBuilder::new("sleep")
+      .args(vec!["10".to_string()])
+      .spawn()
+      .unwrap();
+    child.kill();
+    assert_eq!(
+      events.recv().await,
+      Some(CommandEvent::Terminated(TerminatedPayload {
+        code: None,
+        signal: Some(9)
+      }))
+    );
+  }
+
   #[test]
   // test the command_path function
   fn check_command_path() {
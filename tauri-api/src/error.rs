@@ -28,9 +28,35 @@ pub enum Error {
   /// Invalid HTTO header.
   #[error("{0}")]
   HttpHeader(#[from] reqwest::header::InvalidHeaderName),
+  /// Invalid HTTP header value.
+  #[error("{0}")]
+  HttpHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
   /// Failed to serialize header value as string.
   #[error("failed to convert response header value to string")]
   HttpHeaderToString(#[from] reqwest::header::ToStrError),
+  /// The downloaded file checksum doesn't match the expected one.
+  #[error("checksum mismatch: expected {expected}, found {actual}")]
+  ChecksumMismatch {
+    /// The expected SHA-256 checksum.
+    expected: String,
+    /// The SHA-256 checksum of the downloaded file.
+    actual: String,
+  },
+  /// The URL couldn't be parsed.
+  #[error("invalid url {0}")]
+  InvalidUrl(String),
+  /// The URL isn't allowed by the HTTP scope.
+  #[error("url not allowed by the HTTP scope: {0}")]
+  UrlNotAllowed(String),
+  /// The URL scheme isn't allowed to be opened.
+  #[error("url scheme not allowed to be opened: {0}")]
+  SchemeNotAllowed(String),
+  /// The HTTP scope pattern is invalid.
+  #[error("invalid HTTP scope pattern: {0}")]
+  InvalidScopePattern(String),
+  /// Failed to load or save a cookie jar.
+  #[error("cookie jar error: {0}")]
+  CookieJar(String),
   /// HTTP form to must be an object.
   #[error("http form must be an object")]
   InvalidHttpForm,
@@ -61,4 +87,24 @@ pub enum Error {
   #[cfg(feature = "global-shortcut")]
   #[error("shortcut error: {0}")]
   Shortcut(#[from] tauri_hotkey::Error),
+  /// WebSocket error.
+  #[cfg(feature = "websocket")]
+  #[error("websocket error: {0}")]
+  WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+  /// File system watcher error.
+  #[cfg(feature = "fs-watch")]
+  #[error("watch error: {0}")]
+  Watch(#[from] notify::Error),
+  /// Clipboard error.
+  #[cfg(feature = "clipboard")]
+  #[error("clipboard error: {0}")]
+  Clipboard(String),
+}
+
+#[cfg(feature = "websocket")]
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+  fn from(error: tokio_tungstenite::tungstenite::Error) -> Self {
+    // boxed since the tungstenite error is much larger than the other ones
+    Self::WebSocket(Box::new(error))
+  }
 }
@@ -1,10 +1,16 @@
 mod extract;
 mod file_move;
+mod handle;
 
-use std::{fs, path::Path};
+use std::{
+  fs,
+  io::Write,
+  path::{Path, PathBuf},
+};
 
 pub use extract::*;
 pub use file_move::*;
+pub use handle::*;
 
 /// Reads a string file.
 pub fn read_string<P: AsRef<Path>>(file: P) -> crate::Result<String> {
@@ -16,6 +22,26 @@ pub fn read_binary<P: AsRef<Path>>(file: P) -> crate::Result<Vec<u8>> {
   fs::read(file).map_err(Into::into)
 }
 
+/// Writes the contents to the file atomically.
+///
+/// The data is written to a temporary file on the same directory and then renamed to the destination path,
+/// so readers never see a partially written file. The parent directory is created if it doesn't exist.
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(file: P, contents: C) -> crate::Result<()> {
+  let file = file.as_ref();
+  let parent = match file.parent() {
+    Some(parent) if parent != Path::new("") => parent.to_path_buf(),
+    _ => PathBuf::from("."),
+  };
+  fs::create_dir_all(&parent)?;
+  let mut temp_file = tempfile::NamedTempFile::new_in(&parent)?;
+  temp_file.write_all(contents.as_ref())?;
+  temp_file.as_file().sync_all()?;
+  temp_file
+    .persist(file)
+    .map_err(|e| crate::Error::Io(e.error))?;
+  Ok(())
+}
+
 #[cfg(test)]
 mod test {
   use super::*;
@@ -74,6 +100,19 @@ mod test {
     }
   }
 
+  #[test]
+  fn check_write_atomic() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("nested").join("atomic.txt");
+
+    write_atomic(&file, "first").unwrap();
+    write_atomic(&file, "second").unwrap();
+
+    assert_eq!(read_string(&file).unwrap(), "second".to_string());
+    // only the destination file is left on the directory
+    assert_eq!(fs::read_dir(file.parent().unwrap()).unwrap().count(), 1);
+  }
+
   #[test]
   fn check_read_binary_fail() {
     let file = String::from("test/");
@@ -0,0 +1,180 @@
+use serde::Deserialize;
+
+use std::{
+  fs::File,
+  io::{self, Read, Seek, Write},
+  path::Path,
+};
+
+/// The maximum capacity reserved up front by a read, so a large `len` doesn't allocate more than the file holds.
+/// The buffer still grows to `len` bytes if there's enough data.
+const MAX_READ_CAPACITY: usize = 64 * 1024;
+
+/// The modes a file is opened with. The file is opened for reading if no access mode is set.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOptions {
+  /// Opens the file for reading.
+  #[serde(default)]
+  pub read: bool,
+  /// Opens the file for writing.
+  #[serde(default)]
+  pub write: bool,
+  /// Opens the file for writing, always at its end.
+  #[serde(default)]
+  pub append: bool,
+  /// Truncates the file to 0 bytes when opening it for writing.
+  #[serde(default)]
+  pub truncate: bool,
+  /// Creates the file if it doesn't exist.
+  #[serde(default)]
+  pub create: bool,
+  /// Creates the file, failing if it already exists.
+  #[serde(default)]
+  pub create_new: bool,
+}
+
+/// The position a file cursor is moved to.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "from", content = "offset", rename_all = "camelCase")]
+pub enum SeekFrom {
+  /// An offset from the start of the file.
+  Start(u64),
+  /// An offset from the end of the file.
+  End(i64),
+  /// An offset from the current position.
+  Current(i64),
+}
+
+impl From<SeekFrom> for io::SeekFrom {
+  fn from(from: SeekFrom) -> Self {
+    match from {
+      SeekFrom::Start(offset) => Self::Start(offset),
+      SeekFrom::End(offset) => Self::End(offset),
+      SeekFrom::Current(offset) => Self::Current(offset),
+    }
+  }
+}
+
+/// An open file, read and written incrementally. The file is closed when the handle is dropped.
+///
+/// # Examples
+/// ```no_run
+/// use tauri_api::file::{FileHandle, OpenOptions};
+///
+/// # fn run() -> tauri_api::Result<()> {
+/// let mut log = FileHandle::open(
+///   "app.log",
+///   &OpenOptions {
+///     append: true,
+///     create: true,
+///     ..Default::default()
+///   },
+/// )?;
+/// log.write(b"started\n", None)?;
+/// log.sync()?;
+///
+/// let mut video = FileHandle::open("video.mp4", &Default::default())?;
+/// let chunk = video.read(64 * 1024, Some(1024 * 1024))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileHandle {
+  file: File,
+}
+
+impl FileHandle {
+  /// Opens the file with the given modes.
+  pub fn open<P: AsRef<Path>>(path: P, options: &OpenOptions) -> crate::Result<Self> {
+    let read = options.read || !(options.write || options.append);
+    let file = std::fs::OpenOptions::new()
+      .read(read)
+      .write(options.write)
+      .append(options.append)
+      .truncate(options.truncate)
+      .create(options.create)
+      .create_new(options.create_new)
+      .open(path)?;
+    Ok(Self { file })
+  }
+
+  /// Reads up to `len` bytes, from the given offset or from the cursor position.
+  ///
+  /// Less bytes are returned if the end of the file is reached. The cursor is moved after the bytes read.
+  pub fn read(&mut self, len: usize, offset: Option<u64>) -> crate::Result<Vec<u8>> {
+    if let Some(offset) = offset {
+      self.file.seek(io::SeekFrom::Start(offset))?;
+    }
+    let mut data = Vec::with_capacity(len.min(MAX_READ_CAPACITY));
+    (&mut self.file).take(len as u64).read_to_end(&mut data)?;
+    Ok(data)
+  }
+
+  /// Writes the data at the given offset or at the cursor position, returning the number of bytes written.
+  ///
+  /// The data is always written at the end of the file if it was opened in append mode.
+  pub fn write(&mut self, data: &[u8], offset: Option<u64>) -> crate::Result<usize> {
+    if let Some(offset) = offset {
+      self.file.seek(io::SeekFrom::Start(offset))?;
+    }
+    self.file.write_all(data)?;
+    Ok(data.len())
+  }
+
+  /// Moves the cursor, returning its new position from the start of the file.
+  pub fn seek(&mut self, from: SeekFrom) -> crate::Result<u64> {
+    self.file.seek(from.into()).map_err(Into::into)
+  }
+
+  /// Truncates or extends the file to the given length. The cursor isn't moved.
+  pub fn truncate(&self, len: u64) -> crate::Result<()> {
+    self.file.set_len(len).map_err(Into::into)
+  }
+
+  /// Flushes the data and the metadata of the file to the disk.
+  pub fn sync(&self) -> crate::Result<()> {
+    self.file.sync_all().map_err(Into::into)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn check_file_handle() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("handle.txt");
+    let options = OpenOptions {
+      read: true,
+      write: true,
+      create: true,
+      ..Default::default()
+    };
+    let mut file = FileHandle::open(&path, &options).unwrap();
+
+    assert_eq!(file.write(b"hello tauri", None).unwrap(), 11);
+    assert_eq!(file.read(5, Some(6)).unwrap(), b"tauri".to_vec());
+    file.write(b"H", Some(0)).unwrap();
+    assert_eq!(file.seek(SeekFrom::Current(0)).unwrap(), 1);
+    assert_eq!(file.seek(SeekFrom::End(-5)).unwrap(), 6);
+    // the read stops at the end of the file
+    assert_eq!(file.read(100, None).unwrap(), b"tauri".to_vec());
+    // a huge length doesn't allocate it up front
+    assert_eq!(file.read(usize::MAX, Some(0)).unwrap().len(), 11);
+    file.truncate(5).unwrap();
+    file.sync().unwrap();
+    drop(file);
+
+    let append = OpenOptions {
+      append: true,
+      ..Default::default()
+    };
+    let mut file = FileHandle::open(&path, &append).unwrap();
+    // the offset is ignored in append mode
+    file.write(b"!", Some(0)).unwrap();
+    drop(file);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "Hello!");
+  }
+}
@@ -1,10 +1,53 @@
-use bytes::Bytes;
-use reqwest::{header::HeaderName, redirect::Policy, Method};
+mod cache;
+mod cookie_jar;
+mod scope;
+
+use crate::path::BaseDirectory;
+use cache::{CacheEntry, HttpCache};
+
+pub use bytes::Bytes;
+pub use cookie_jar::*;
+pub use scope::*;
+
+use futures_util::{stream, Stream, TryStreamExt};
+use reqwest::{
+  header::{
+    HeaderName, HeaderValue, AUTHORIZATION, CONTENT_RANGE, COOKIE, ETAG, IF_MODIFIED_SINCE,
+    IF_NONE_MATCH, IF_RANGE, LAST_MODIFIED, RANGE,
+  },
+  multipart::{Form, Part},
+  redirect::Policy,
+  tls, Certificate, Identity, Method, Proxy, StatusCode,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_repr::{Deserialize_repr, Serialize_repr};
+use sha2::{Digest, Sha256};
+use tokio::sync::Notify;
+use tokio_util::io::ReaderStream;
+
+use std::{
+  collections::HashMap,
+  fs::{self, File, OpenOptions},
+  io::Write,
+  path::{Path, PathBuf},
+  pin::Pin,
+  sync::{
+    atomic::{AtomicU64, AtomicU8, Ordering},
+    Arc, Mutex,
+  },
+  time::{Duration, Instant},
+};
+
+/// The minimum interval between two download or upload progress reports.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
 
-use std::{collections::HashMap, path::PathBuf, time::Duration};
+/// The maximum number of redirections followed by default, same as reqwest.
+const DEFAULT_MAX_REDIRECTIONS: usize = 10;
+
+const DOWNLOAD_RUNNING: u8 = 0;
+const DOWNLOAD_PAUSED: u8 = 1;
+const DOWNLOAD_CANCELLED: u8 = 2;
 
 /// Client builder.
 #[derive(Default, Deserialize)]
@@ -14,6 +57,134 @@ pub struct ClientBuilder {
   pub max_redirections: Option<usize>,
   /// Connect timeout in seconds for the request
   pub connect_timeout: Option<u64>,
+  /// The proxy configuration. Defaults to the system proxy.
+  pub proxy: Option<ProxyConfig>,
+  /// The name of the cookie jar persisted to the app directory. Cookies aren't stored if not set.
+  pub cookie_jar: Option<String>,
+  /// Paths to additional trusted root certificates, PEM bundles or DER encoded.
+  pub root_certificates: Option<Vec<PathBuf>>,
+  /// The client certificate used for mutual TLS.
+  pub client_certificate: Option<ClientCertificate>,
+  /// The minimum TLS version of the connections.
+  pub min_tls_version: Option<TlsVersion>,
+  /// Whether GET responses are cached on disk, honoring the `Cache-Control` and `Expires` headers
+  /// and revalidating stale responses with their `ETag` and `Last-Modified` headers.
+  #[serde(default)]
+  pub cache: bool,
+  /// The directory of the response cache. Defaults to `{cache_dir}/{app_name}/http/{partition}`,
+  /// where the partition is derived from the cookie jar and the client certificate.
+  /// Requests carrying credentials only use responses marked as `public`.
+  #[serde(skip)]
+  pub cache_dir: Option<PathBuf>,
+  /// The URLs the client is allowed to access, including redirections. Every URL is allowed if not set.
+  #[serde(skip)]
+  pub scope: Option<Scope>,
+}
+
+/// The proxy configuration.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyConfig {
+  /// The proxy URL for all requests.
+  pub all: Option<String>,
+  /// The proxy URL for HTTP requests.
+  pub http: Option<String>,
+  /// The proxy URL for HTTPS requests.
+  pub https: Option<String>,
+  /// The proxy basic authentication username.
+  pub username: Option<String>,
+  /// The proxy basic authentication password.
+  pub password: Option<String>,
+  /// Whether the proxy is detected from the `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables
+  /// when no proxy URL is set. Defaults to true.
+  pub system: Option<bool>,
+}
+
+impl ProxyConfig {
+  fn apply(
+    self,
+    mut client_builder: reqwest::ClientBuilder,
+  ) -> crate::Result<reqwest::ClientBuilder> {
+    let mut proxies = Vec::new();
+    if let Some(all) = &self.all {
+      proxies.push(Proxy::all(all)?);
+    }
+    if let Some(http) = &self.http {
+      proxies.push(Proxy::http(http)?);
+    }
+    if let Some(https) = &self.https {
+      proxies.push(Proxy::https(https)?);
+    }
+
+    if proxies.is_empty() && !self.system.unwrap_or(true) {
+      client_builder = client_builder.no_proxy();
+    }
+    for mut proxy in proxies {
+      if let Some(username) = &self.username {
+        proxy = proxy.basic_auth(username, self.password.as_deref().unwrap_or_default());
+      }
+      client_builder = client_builder.proxy(proxy);
+    }
+    Ok(client_builder)
+  }
+}
+
+/// A client certificate for mutual TLS.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCertificate {
+  /// The path to the PKCS #12 archive containing the certificate and its private key.
+  pub path: PathBuf,
+  /// The archive password.
+  #[serde(default)]
+  pub password: String,
+}
+
+/// A TLS protocol version.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TlsVersion {
+  /// TLS 1.0
+  #[serde(rename = "1.0")]
+  Tls1_0,
+  /// TLS 1.1
+  #[serde(rename = "1.1")]
+  Tls1_1,
+  /// TLS 1.2
+  #[serde(rename = "1.2")]
+  Tls1_2,
+  /// TLS 1.3
+  #[serde(rename = "1.3")]
+  Tls1_3,
+}
+
+impl From<TlsVersion> for tls::Version {
+  fn from(version: TlsVersion) -> Self {
+    match version {
+      TlsVersion::Tls1_0 => Self::TLS_1_0,
+      TlsVersion::Tls1_1 => Self::TLS_1_1,
+      TlsVersion::Tls1_2 => Self::TLS_1_2,
+      TlsVersion::Tls1_3 => Self::TLS_1_3,
+    }
+  }
+}
+
+/// Reads the certificates of a PEM bundle or a DER encoded file.
+fn read_certificates(path: &Path) -> crate::Result<Vec<Certificate>> {
+  const PEM_CERTIFICATE_HEADER: &str = "-----BEGIN CERTIFICATE-----";
+  let contents = fs::read(path)?;
+  if contents.starts_with(b"-----BEGIN") {
+    // each certificate of the bundle is parsed on its own, since `Certificate::from_pem` only reads the first one
+    String::from_utf8_lossy(&contents)
+      .split(PEM_CERTIFICATE_HEADER)
+      .skip(1)
+      .map(|certificate| {
+        Certificate::from_pem(format!("{}{}", PEM_CERTIFICATE_HEADER, certificate).as_bytes())
+          .map_err(Into::into)
+      })
+      .collect()
+  } else {
+    Ok(vec![Certificate::from_der(&contents)?])
+  }
 }
 
 impl ClientBuilder {
@@ -34,11 +205,75 @@ impl ClientBuilder {
     self
   }
 
+  /// Sets the proxy configuration.
+  pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+    self.proxy = Some(proxy);
+    self
+  }
+
+  /// Sets the name of the cookie jar persisted to the app directory.
+  pub fn cookie_jar(mut self, cookie_jar: impl Into<String>) -> Self {
+    self.cookie_jar = Some(cookie_jar.into());
+    self
+  }
+
+  /// Adds a trusted root certificate file, a PEM bundle or DER encoded.
+  pub fn root_certificate(mut self, path: impl Into<PathBuf>) -> Self {
+    self
+      .root_certificates
+      .get_or_insert_with(Default::default)
+      .push(path.into());
+    self
+  }
+
+  /// Sets the client certificate used for mutual TLS.
+  pub fn client_certificate(mut self, client_certificate: ClientCertificate) -> Self {
+    self.client_certificate = Some(client_certificate);
+    self
+  }
+
+  /// Sets the minimum TLS version of the connections.
+  pub fn min_tls_version(mut self, min_tls_version: TlsVersion) -> Self {
+    self.min_tls_version = Some(min_tls_version);
+    self
+  }
+
+  /// Sets whether GET responses are cached on disk.
+  pub fn cache(mut self, cache: bool) -> Self {
+    self.cache = cache;
+    self
+  }
+
+  /// Caches the GET responses on the given directory.
+  pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+    self.cache = true;
+    self.cache_dir = Some(cache_dir.into());
+    self
+  }
+
+  /// Sets the URLs the client is allowed to access.
+  pub fn scope(mut self, scope: Scope) -> Self {
+    self.scope = Some(scope);
+    self
+  }
+
   /// Builds the ClientOptions.
   pub fn build(self) -> crate::Result<Client> {
     let mut client_builder = reqwest::Client::builder();
 
-    if let Some(max_redirections) = self.max_redirections {
+    if let Some(scope) = self.scope.clone() {
+      let max_redirections = self.max_redirections.unwrap_or(DEFAULT_MAX_REDIRECTIONS);
+      client_builder = client_builder.redirect(Policy::custom(move |attempt| {
+        if !scope.is_allowed(attempt.url()) {
+          let url = attempt.url().to_string();
+          attempt.error(crate::Error::UrlNotAllowed(url))
+        } else if attempt.previous().len() > max_redirections {
+          attempt.error("too many redirects")
+        } else {
+          attempt.follow()
+        }
+      }));
+    } else if let Some(max_redirections) = self.max_redirections {
       client_builder = client_builder.redirect(Policy::limited(max_redirections))
     }
 
@@ -46,23 +281,187 @@ impl ClientBuilder {
       client_builder = client_builder.connect_timeout(Duration::from_secs(connect_timeout));
     }
 
-    let client = client_builder.build()?;
-    Ok(Client(client))
+    if let Some(proxy) = self.proxy {
+      client_builder = proxy.apply(client_builder)?;
+    }
+
+    if let Some(cookie_jar) = &self.cookie_jar {
+      client_builder = client_builder.cookie_provider(CookieJar::named(cookie_jar)?);
+    }
+
+    if let Some(root_certificates) = &self.root_certificates {
+      for path in root_certificates {
+        for certificate in read_certificates(path)? {
+          client_builder = client_builder.add_root_certificate(certificate);
+        }
+      }
+    }
+
+    if let Some(client_certificate) = &self.client_certificate {
+      let archive = fs::read(&client_certificate.path)?;
+      let identity = Identity::from_pkcs12_der(&archive, &client_certificate.password)?;
+      client_builder = client_builder.identity(identity);
+    }
+
+    if let Some(min_tls_version) = self.min_tls_version {
+      client_builder = client_builder.min_tls_version(min_tls_version.into());
+    }
+
+    let cache = if self.cache {
+      let cache_dir = match self.cache_dir {
+        Some(cache_dir) => cache_dir,
+        None => HttpCache::default_dir(
+          self.cookie_jar.as_deref(),
+          self
+            .client_certificate
+            .as_ref()
+            .map(|client_certificate| client_certificate.path.as_path()),
+        )?,
+      };
+      Some(HttpCache::new(cache_dir))
+    } else {
+      None
+    };
+
+    Ok(Client {
+      client: client_builder.build()?,
+      scope: self.scope,
+      cache,
+      cookies: self.cookie_jar.is_some(),
+    })
   }
 }
 
 /// The HTTP client.
 #[derive(Clone)]
-pub struct Client(reqwest::Client);
+pub struct Client {
+  client: reqwest::Client,
+  scope: Option<Scope>,
+  cache: Option<HttpCache>,
+  /// Whether the requests carry the cookies of a cookie jar.
+  cookies: bool,
+}
 
 impl Client {
+  /// Executes the request if its URL is allowed by the client scope.
+  async fn execute(&self, request: reqwest::Request) -> crate::Result<reqwest::Response> {
+    if let Some(scope) = &self.scope {
+      if !scope.is_allowed(request.url()) {
+        return Err(crate::Error::UrlNotAllowed(request.url().to_string()));
+      }
+    }
+    self.client.execute(request).await.map_err(Into::into)
+  }
+
+  /// Executes a GET request, using the cached response if it's fresh or wasn't modified.
+  ///
+  /// Requests carrying credentials only use and store responses marked as `public`.
+  async fn execute_cached(
+    &self,
+    cache: &HttpCache,
+    mut request: reqwest::Request,
+    response_type: ResponseType,
+  ) -> crate::Result<Response> {
+    let url = request.url().clone();
+    let request_headers = request.headers().clone();
+    let private = self.cookies
+      || request_headers.contains_key(AUTHORIZATION)
+      || request_headers.contains_key(COOKIE);
+    let cached = cache
+      .get(&url, &request_headers)
+      .filter(|(entry, _)| !private || entry.is_public());
+    if let Some((entry, body)) = &cached {
+      if entry.is_fresh() {
+        return Ok(Response::from_cache_entry(
+          response_type,
+          entry,
+          body.clone(),
+          true,
+        ));
+      }
+      // revalidates the stale response
+      if let Some(etag) = entry
+        .etag()
+        .and_then(|etag| HeaderValue::from_str(etag).ok())
+      {
+        request.headers_mut().insert(IF_NONE_MATCH, etag);
+      }
+      if let Some(last_modified) = entry
+        .last_modified()
+        .and_then(|last_modified| HeaderValue::from_str(last_modified).ok())
+      {
+        request
+          .headers_mut()
+          .insert(IF_MODIFIED_SINCE, last_modified);
+      }
+    }
+
+    let response = self.execute(request).await?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+      if let Some((mut entry, body)) = cached {
+        if entry.update(response.headers()).is_some() {
+          if let Err(e) = cache.put(&url, &entry, None) {
+            log::warn!("failed to update the cached response of {}: {}", url, e);
+          }
+        }
+        return Ok(Response::from_cache_entry(
+          response_type,
+          &entry,
+          body,
+          true,
+        ));
+      }
+    }
+
+    let response = response.error_for_status()?;
+    let entry = if response.status() == StatusCode::OK {
+      CacheEntry::new(
+        response.url(),
+        response.status().as_u16(),
+        response.headers(),
+        &request_headers,
+      )
+      .filter(|entry| !private || entry.is_public())
+    } else {
+      None
+    };
+    match entry {
+      Some(entry) => {
+        let body = response.bytes().await?.to_vec();
+        // the cache is best-effort, so the response is still returned if it can't be stored
+        if let Err(e) = cache.put(&url, &entry, Some(&body)) {
+          log::warn!("failed to cache the response of {}: {}", url, e);
+        }
+        Ok(Response::from_cache_entry(
+          response_type,
+          &entry,
+          body,
+          false,
+        ))
+      }
+      None => Ok(Response::new(response_type, response)),
+    }
+  }
+
   /// Executes an HTTP request
   ///
   /// The response will be transformed to String,
   /// If reading the response as binary, the byte array will be serialized using serde_json
   pub async fn send(&self, request: HttpRequestBuilder) -> crate::Result<Response> {
+    self.send_with_progress(request, |_| {}).await
+  }
+
+  /// Executes an HTTP request, reporting the upload progress of its multipart form body.
+  ///
+  /// The `on_progress` closure is called at most every 100 milliseconds and once the body is fully sent.
+  pub async fn send_with_progress<F: Fn(UploadProgress) + Send + Sync + 'static>(
+    &self,
+    request: HttpRequestBuilder,
+    on_progress: F,
+  ) -> crate::Result<Response> {
     let method = Method::from_bytes(request.method.to_uppercase().as_bytes())?;
-    let mut request_builder = self.0.request(method, &request.url);
+    let cacheable = method == Method::GET && request.body.is_none();
+    let mut request_builder = self.client.request(method, &request.url);
 
     if let Some(query) = request.query {
       request_builder = request_builder.query(&query);
@@ -79,32 +478,366 @@ impl Client {
       request_builder = request_builder.timeout(Duration::from_secs(timeout));
     }
 
-    let response = if let Some(body) = request.body {
-      match body {
-        Body::Bytes(data) => request_builder.body(Bytes::from(data)).send().await?,
-        Body::Text(text) => request_builder.body(Bytes::from(text)).send().await?,
-        Body::Json(json) => request_builder.json(&json).send().await?,
+    if let Some(body) = request.body {
+      request_builder = match body {
+        Body::Bytes(data) => request_builder.body(Bytes::from(data)),
+        Body::Text(text) => request_builder.body(Bytes::from(text)),
+        Body::Json(json) => request_builder.json(&json),
         Body::Form(form_body) => {
-          let mut form = Vec::new();
-          for (name, part) in form_body.0 {
-            match part {
-              FormPart::Bytes(bytes) => form.push((name, serde_json::to_string(&bytes)?)),
-              FormPart::File(file_path) => form.push((name, serde_json::to_string(&file_path)?)),
-              FormPart::Text(text) => form.push((name, text)),
-            }
-          }
-          request_builder.form(&form).send().await?
+          let form = form_body.into_multipart(on_progress).await?;
+          request_builder.multipart(form)
         }
+      };
+    }
+
+    let response_type = request.response_type.unwrap_or(ResponseType::Json);
+    let request = request_builder.build()?;
+    match &self.cache {
+      Some(cache) if cacheable => self.execute_cached(cache, request, response_type).await,
+      _ => {
+        let response = self.execute(request).await?.error_for_status()?;
+        Ok(Response::new(response_type, response))
       }
+    }
+  }
+
+  /// Downloads a file, streaming the response body to the request path.
+  ///
+  /// If `resume` is set and the file already exists, only the missing bytes are requested with a `Range` header.
+  /// The partial file is only resumed if the server sent a validator (a strong `ETag` or `Last-Modified`) with it,
+  /// which is stored next to the file and sent with `If-Range`, so the download starts over if the remote file changed.
+  /// The `on_progress` closure is called at most every 100 milliseconds and once the download finishes.
+  ///
+  /// # Examples
+  /// ```no_run
+  /// use tauri_api::http::{ClientBuilder, DownloadHandle, DownloadRequestBuilder};
+  /// async fn run() {
+  ///   let client = ClientBuilder::new().build().unwrap();
+  ///   let request = DownloadRequestBuilder::new("http://example.com/file.zip", "/tmp/file.zip");
+  ///   let handle = DownloadHandle::new();
+  ///   let status = client
+  ///     .download(request, &handle, |progress| {
+  ///       println!("{} of {:?} bytes", progress.downloaded, progress.total)
+  ///     })
+  ///     .await;
+  /// }
+  /// ```
+  pub async fn download<F: FnMut(DownloadProgress)>(
+    &self,
+    request: DownloadRequestBuilder,
+    handle: &DownloadHandle,
+    mut on_progress: F,
+  ) -> crate::Result<DownloadStatus> {
+    let validator_path = resume_validator_path(&request.path);
+    let mut hasher = Sha256::new();
+    let mut downloaded = 0;
+    let mut validator = None;
+    if request.resume {
+      if let (Ok(mut partial_file), Ok(partial_validator)) = (
+        File::open(&request.path),
+        fs::read_to_string(&validator_path),
+      ) {
+        downloaded = std::io::copy(&mut partial_file, &mut hasher)?;
+        validator = Some(partial_validator);
+      }
+    }
+    let range = match &validator {
+      Some(validator) if downloaded > 0 => Some((downloaded, validator.as_str())),
+      _ => None,
+    };
+
+    let mut response = self
+      .execute(self.download_request(&request, range)?)
+      .await?;
+    if range.is_some() && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+      if unsatisfied_range_length(&response) == Some(downloaded) {
+        // the partial file is already complete
+        on_progress(DownloadProgress {
+          downloaded,
+          total: Some(downloaded),
+        });
+        let _ = fs::remove_file(&validator_path);
+        verify_checksum(&request, hasher)?;
+        return Ok(DownloadStatus::Completed);
+      }
+      // the partial file is longer than the remote file, so we start over
+      response = self.execute(self.download_request(&request, None)?).await?;
+    }
+    let mut response = response.error_for_status()?;
+
+    let mut file = if range.is_some() && response.status() == StatusCode::PARTIAL_CONTENT {
+      OpenOptions::new().append(true).open(&request.path)?
     } else {
-      request_builder.send().await?
+      // the server ignored the range, e.g. if the validator doesn't match (or there's nothing to resume), so we start over
+      downloaded = 0;
+      hasher = Sha256::new();
+      if let Some(parent) = request.path.parent() {
+        fs::create_dir_all(parent)?;
+      }
+      match resume_validator(&response) {
+        Some(validator) => crate::file::write_atomic(&validator_path, validator)?,
+        None => {
+          let _ = fs::remove_file(&validator_path);
+        }
+      }
+      File::create(&request.path)?
     };
 
-    let response = response.error_for_status()?;
-    Ok(Response(
-      request.response_type.unwrap_or(ResponseType::Json),
-      response,
-    ))
+    let total = response.content_length().map(|length| length + downloaded);
+    let mut last_progress = Instant::now();
+    on_progress(DownloadProgress { downloaded, total });
+
+    loop {
+      // waits for the control signal too, so a stalled transfer can still be paused or cancelled
+      let chunk = tokio::select! {
+        chunk = response.chunk() => chunk?,
+        _ = handle.stopped() => None,
+      };
+      match handle.0.status.load(Ordering::SeqCst) {
+        DOWNLOAD_PAUSED => {
+          file.sync_all()?;
+          return Ok(DownloadStatus::Paused);
+        }
+        DOWNLOAD_CANCELLED => {
+          drop(file);
+          fs::remove_file(&request.path)?;
+          let _ = fs::remove_file(&validator_path);
+          return Ok(DownloadStatus::Cancelled);
+        }
+        _ => {}
+      }
+      let chunk = match chunk {
+        Some(chunk) => chunk,
+        None => break,
+      };
+
+      file.write_all(&chunk)?;
+      hasher.update(&chunk);
+      downloaded += chunk.len() as u64;
+
+      if last_progress.elapsed() >= PROGRESS_INTERVAL {
+        last_progress = Instant::now();
+        on_progress(DownloadProgress { downloaded, total });
+      }
+    }
+
+    file.sync_all()?;
+    drop(file);
+    on_progress(DownloadProgress {
+      downloaded,
+      total: Some(downloaded),
+    });
+    let _ = fs::remove_file(&validator_path);
+    verify_checksum(&request, hasher)?;
+    Ok(DownloadStatus::Completed)
+  }
+
+  /// Builds the download request, resuming from the given offset if the remote file still matches the validator.
+  fn download_request(
+    &self,
+    request: &DownloadRequestBuilder,
+    range: Option<(u64, &str)>,
+  ) -> crate::Result<reqwest::Request> {
+    let mut request_builder = self.client.get(&request.url);
+    if let Some(headers) = &request.headers {
+      for (header, header_value) in headers.iter() {
+        request_builder =
+          request_builder.header(HeaderName::from_bytes(header.as_bytes())?, header_value);
+      }
+    }
+    if let Some(timeout) = request.timeout {
+      request_builder = request_builder.timeout(Duration::from_secs(timeout));
+    }
+    if let Some((offset, validator)) = range {
+      request_builder = request_builder
+        .header(RANGE, format!("bytes={}-", offset))
+        .header(IF_RANGE, validator);
+    }
+    request_builder.build().map_err(Into::into)
+  }
+}
+
+/// The path of the file storing the validator of a partial download.
+fn resume_validator_path(path: &Path) -> PathBuf {
+  let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+  file_name.push(".resume");
+  path.with_file_name(file_name)
+}
+
+/// The validator sent with `If-Range` to resume a download: the `ETag` if it's strong, or the `Last-Modified` date.
+fn resume_validator(response: &reqwest::Response) -> Option<String> {
+  let headers = response.headers();
+  headers
+    .get(ETAG)
+    .and_then(|etag| etag.to_str().ok())
+    .filter(|etag| !etag.starts_with("W/"))
+    .or_else(|| {
+      headers
+        .get(LAST_MODIFIED)
+        .and_then(|last_modified| last_modified.to_str().ok())
+    })
+    .map(Into::into)
+}
+
+/// The length of the remote file sent on a `416 Range Not Satisfiable` response, as `Content-Range: bytes */<length>`.
+fn unsatisfied_range_length(response: &reqwest::Response) -> Option<u64> {
+  response
+    .headers()
+    .get(CONTENT_RANGE)?
+    .to_str()
+    .ok()?
+    .strip_prefix("bytes */")?
+    .trim()
+    .parse()
+    .ok()
+}
+
+/// Checks the downloaded file against the expected SHA-256 checksum, removing it on mismatch.
+fn verify_checksum(request: &DownloadRequestBuilder, hasher: Sha256) -> crate::Result<()> {
+  if let Some(expected) = &request.sha256 {
+    let actual = hex::encode(hasher.finalize());
+    if !actual.eq_ignore_ascii_case(expected) {
+      fs::remove_file(&request.path)?;
+      return Err(crate::Error::ChecksumMismatch {
+        expected: expected.to_string(),
+        actual,
+      });
+    }
+  }
+  Ok(())
+}
+
+/// The state of a download handle.
+#[derive(Default)]
+struct DownloadState {
+  /// The download status, running, paused or cancelled.
+  status: AtomicU8,
+  /// Notified when the download is paused or cancelled.
+  stopped: Notify,
+}
+
+/// Controls a running download.
+///
+/// The state is shared between clones, so the handle can be used to pause or cancel the download from another task.
+#[derive(Clone, Default)]
+pub struct DownloadHandle(Arc<DownloadState>);
+
+impl DownloadHandle {
+  /// Creates a new download handle.
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Pauses the download. The partially downloaded file is kept so the download can be resumed later.
+  pub fn pause(&self) {
+    self.stop(DOWNLOAD_PAUSED);
+  }
+
+  /// Cancels the download. The partially downloaded file is removed.
+  pub fn cancel(&self) {
+    self.stop(DOWNLOAD_CANCELLED);
+  }
+
+  /// Whether the download is still running, i.e. it wasn't paused nor cancelled.
+  pub fn is_running(&self) -> bool {
+    self.0.status.load(Ordering::SeqCst) == DOWNLOAD_RUNNING
+  }
+
+  fn stop(&self, status: u8) {
+    self.0.status.store(status, Ordering::SeqCst);
+    // only the download waits on the handle, and the permit is kept if it isn't waiting yet
+    self.0.stopped.notify_one();
+  }
+
+  /// Resolves once the download is paused or cancelled.
+  async fn stopped(&self) {
+    if self.is_running() {
+      self.0.stopped.notified().await;
+    }
+  }
+}
+
+/// The progress of a download.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+  /// The number of bytes downloaded so far, including the resumed bytes.
+  pub downloaded: u64,
+  /// The size of the file. `None` if the server didn't send the content length.
+  pub total: Option<u64>,
+}
+
+/// The status of a finished download.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DownloadStatus {
+  /// The file was fully downloaded.
+  Completed,
+  /// The download was paused. The partial file was kept.
+  Paused,
+  /// The download was cancelled. The partial file was removed.
+  Cancelled,
+}
+
+fn default_resume() -> bool {
+  true
+}
+
+/// The builder for a download request.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadRequestBuilder {
+  /// The file URL
+  pub url: String,
+  /// The path where the file is written
+  pub path: PathBuf,
+  /// The request headers
+  pub headers: Option<HashMap<String, String>>,
+  /// Timeout for the whole request
+  pub timeout: Option<u64>,
+  /// The expected SHA-256 checksum of the file, as a hex string
+  pub sha256: Option<String>,
+  /// Whether an existing partial file should be resumed (defaults to true).
+  /// Only the partial files downloaded from a server sending a validator can be resumed.
+  #[serde(default = "default_resume")]
+  pub resume: bool,
+}
+
+impl DownloadRequestBuilder {
+  /// Initializes a new instance of the DownloadRequestBuilder.
+  pub fn new(url: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+    Self {
+      url: url.into(),
+      path: path.into(),
+      headers: None,
+      timeout: None,
+      sha256: None,
+      resume: true,
+    }
+  }
+
+  /// Sets the request headers.
+  pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+    self.headers = Some(headers);
+    self
+  }
+
+  /// Sets the general request timeout.
+  pub fn timeout(mut self, timeout: u64) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Sets the expected SHA-256 checksum of the file.
+  pub fn sha256(mut self, sha256: impl Into<String>) -> Self {
+    self.sha256 = Some(sha256.into());
+    self
+  }
+
+  /// Sets whether an existing partial file should be resumed.
+  pub fn resume(mut self, resume: bool) -> Self {
+    self.resume = resume;
+    self
   }
 }
 
@@ -124,23 +857,154 @@ pub enum ResponseType {
 #[derive(Deserialize)]
 #[serde(untagged)]
 pub enum FormPart {
-  /// A file path value.
-  File(PathBuf),
+  /// A file, streamed from disk.
+  #[serde(rename_all = "camelCase")]
+  File {
+    /// The file path.
+    file: PathBuf,
+    /// The base directory of the file path, resolved like the `fs` API paths.
+    dir: Option<BaseDirectory>,
+    /// The part mime type. Not set by default.
+    mime: Option<String>,
+    /// The part file name. Defaults to the file name of the path.
+    file_name: Option<String>,
+  },
+  /// A byte array value.
+  #[serde(rename_all = "camelCase")]
+  Bytes {
+    /// The part content.
+    bytes: Vec<u8>,
+    /// The part mime type. Not set by default.
+    mime: Option<String>,
+    /// The part file name. Not set by default.
+    file_name: Option<String>,
+  },
   /// A string value.
   Text(String),
-  /// A byte array value.
-  Bytes(Vec<u8>),
 }
 
-/// Form body definition.
+/// Form body definition, with the parts in the order they're sent.
 #[derive(Deserialize)]
-pub struct FormBody(HashMap<String, FormPart>);
+pub struct FormBody(Vec<(String, FormPart)>);
 
 impl FormBody {
   /// Creates a new form body.
-  pub fn new(data: HashMap<String, FormPart>) -> Self {
+  pub fn new(data: Vec<(String, FormPart)>) -> Self {
     Self(data)
   }
+
+  /// Whether the form has parts read from files.
+  pub fn has_files(&self) -> bool {
+    self
+      .0
+      .iter()
+      .any(|(_, part)| matches!(part, FormPart::File { .. }))
+  }
+
+  /// Builds the `multipart/form-data` body, streaming the parts through the upload progress reporter.
+  async fn into_multipart<F: Fn(UploadProgress) + Send + Sync + 'static>(
+    self,
+    on_progress: F,
+  ) -> crate::Result<Form> {
+    let mut parts = Vec::new();
+    let mut total = 0;
+    for (name, part) in self.0 {
+      let (body, length, mime, file_name) = match part {
+        FormPart::File {
+          file,
+          dir,
+          mime,
+          file_name,
+        } => {
+          let file = crate::path::resolve_path(file, dir)?;
+          let reader = tokio::fs::File::open(&file).await?;
+          let length = reader.metadata().await?.len();
+          let file_name = file_name.or_else(|| {
+            file
+              .file_name()
+              .map(|file_name| file_name.to_string_lossy().into_owned())
+          });
+          let body: PartStream = Box::pin(ReaderStream::new(reader));
+          (body, length, mime, file_name)
+        }
+        FormPart::Bytes {
+          bytes,
+          mime,
+          file_name,
+        } => {
+          let length = bytes.len() as u64;
+          let body: PartStream = Box::pin(stream::once(async { Ok(Bytes::from(bytes)) }));
+          (body, length, mime, file_name)
+        }
+        FormPart::Text(text) => {
+          let length = text.len() as u64;
+          let body: PartStream = Box::pin(stream::once(async { Ok(Bytes::from(text)) }));
+          (body, length, None, None)
+        }
+      };
+      total += length;
+      parts.push((name, body, length, mime, file_name));
+    }
+
+    let reporter = Arc::new(UploadProgressReporter {
+      uploaded: AtomicU64::new(0),
+      total,
+      last_report: Mutex::new(Instant::now()),
+      on_progress: Box::new(on_progress),
+    });
+    let mut form = Form::new();
+    for (name, body, length, mime, file_name) in parts {
+      let reporter = reporter.clone();
+      let body = body.inspect_ok(move |chunk| reporter.advance(chunk.len() as u64));
+      let mut part = Part::stream_with_length(reqwest::Body::wrap_stream(body), length);
+      if let Some(mime) = mime {
+        part = part.mime_str(&mime)?;
+      }
+      if let Some(file_name) = file_name {
+        part = part.file_name(file_name);
+      }
+      form = form.part(name, part);
+    }
+    Ok(form)
+  }
+}
+
+/// The content of a form part.
+type PartStream = Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send + Sync>>;
+
+/// The upload progress of a multipart form body.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProgress {
+  /// The number of bytes of the form parts sent so far.
+  pub uploaded: u64,
+  /// The total size of the form parts.
+  pub total: u64,
+}
+
+/// Counts the bytes sent by the form parts and reports the upload progress.
+struct UploadProgressReporter {
+  uploaded: AtomicU64,
+  total: u64,
+  last_report: Mutex<Instant>,
+  on_progress: Box<dyn Fn(UploadProgress) + Send + Sync>,
+}
+
+impl UploadProgressReporter {
+  fn advance(&self, length: u64) {
+    let uploaded = self.uploaded.fetch_add(length, Ordering::SeqCst) + length;
+    let mut last_report = self
+      .last_report
+      .lock()
+      .expect("Failed to lock upload progress: advance()");
+    if uploaded >= self.total || last_report.elapsed() >= PROGRESS_INTERVAL {
+      *last_report = Instant::now();
+      (self.on_progress)(UploadProgress {
+        uploaded,
+        total: self.total,
+      });
+    }
+  }
 }
 
 /// A body for the request.
@@ -242,31 +1106,95 @@ impl HttpRequestBuilder {
 }
 
 /// The HTTP response.
-pub struct Response(ResponseType, reqwest::Response);
+pub struct Response {
+  response_type: ResponseType,
+  response: reqwest::Response,
+  url: String,
+  from_cache: bool,
+}
 
 impl Response {
-  /// Reads the response and returns its info.
-  pub async fn read(self) -> crate::Result<ResponseData> {
-    let url = self.1.url().to_string();
+  fn new(response_type: ResponseType, response: reqwest::Response) -> Self {
+    Self {
+      response_type,
+      url: response.url().to_string(),
+      response,
+      from_cache: false,
+    }
+  }
+
+  /// Creates a response from a cache entry and its body.
+  fn from_cache_entry(
+    response_type: ResponseType,
+    entry: &CacheEntry,
+    body: Vec<u8>,
+    from_cache: bool,
+  ) -> Self {
+    let mut response = http::Response::new(body);
+    *response.status_mut() = StatusCode::from_u16(entry.status).unwrap_or(StatusCode::OK);
+    *response.headers_mut() = entry.header_map();
+    Self {
+      response_type,
+      response: response.into(),
+      url: entry.url.clone(),
+      from_cache,
+    }
+  }
+
+  /// Whether the response was served from the cache.
+  pub fn from_cache(&self) -> bool {
+    self.from_cache
+  }
+
+  /// Returns the response info without reading its body, leaving `data` as `null`.
+  pub fn head(&self) -> crate::Result<ResponseData> {
     let mut headers = HashMap::new();
-    for (name, value) in self.1.headers() {
+    for (name, value) in self.response.headers() {
       headers.insert(name.as_str().to_string(), value.to_str()?.to_string());
     }
-    let status = self.1.status().as_u16();
-
-    let data = match self.0 {
-      ResponseType::Json => self.1.json().await?,
-      ResponseType::Text => Value::String(self.1.text().await?),
-      ResponseType::Binary => Value::String(serde_json::to_string(&self.1.bytes().await?)?),
-    };
 
     Ok(ResponseData {
-      url,
-      status,
+      url: self.url.clone(),
+      status: self.response.status().as_u16(),
       headers,
-      data,
+      data: Value::Null,
+      from_cache: self.from_cache,
     })
   }
+
+  /// Reads the response and returns its info.
+  pub async fn read(self) -> crate::Result<ResponseData> {
+    let mut response_data = self.head()?;
+    response_data.data = match self.response_type {
+      ResponseType::Json => self.response.json().await?,
+      ResponseType::Text => Value::String(self.response.text().await?),
+      ResponseType::Binary => Value::String(serde_json::to_string(&self.response.bytes().await?)?),
+    };
+    Ok(response_data)
+  }
+
+  /// Converts the response into a stream of its body chunks, as they arrive.
+  ///
+  /// The request is aborted when the stream is dropped.
+  ///
+  /// # Examples
+  /// ```no_run
+  /// use futures_util::StreamExt;
+  /// use tauri_api::http::{ClientBuilder, HttpRequestBuilder};
+  ///
+  /// # async fn run() -> tauri_api::Result<()> {
+  /// let client = ClientBuilder::new().build()?;
+  /// let response = client.send(HttpRequestBuilder::new("GET", "http://localhost/events")).await?;
+  /// let mut stream = response.bytes_stream();
+  /// while let Some(chunk) = stream.next().await {
+  ///   println!("received {} bytes", chunk?.len());
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn bytes_stream(self) -> impl Stream<Item = crate::Result<Bytes>> {
+    self.response.bytes_stream().map_err(Into::into)
+  }
 }
 
 /// The response type.
@@ -277,4 +1205,305 @@ pub struct ResponseData {
   status: u16,
   headers: HashMap<String, String>,
   data: Value,
+  from_cache: bool,
+}
+
+#[cfg(test)]
+mod test {
+  use super::{
+    verify_checksum, ClientBuilder, DownloadHandle, DownloadRequestBuilder, DownloadStatus,
+    FormBody, FormPart, HttpRequestBuilder, ResponseType,
+  };
+  use futures_util::TryStreamExt;
+  use serde_json::json;
+  use sha2::{Digest, Sha256};
+
+  use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+  };
+
+  /// Serves the given responses, one per connection, and records the request heads.
+  fn serve(responses: Vec<&'static str>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/data", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let requests_ = requests.clone();
+    std::thread::spawn(move || {
+      for response in responses {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut head = String::new();
+        let mut reader = BufReader::new(&mut stream);
+        loop {
+          let mut line = String::new();
+          reader.read_line(&mut line).unwrap();
+          if line.trim().is_empty() {
+            break;
+          }
+          head.push_str(&line);
+        }
+        requests_.lock().unwrap().push(head.to_lowercase());
+        stream.write_all(response.as_bytes()).unwrap();
+      }
+    });
+    (url, requests)
+  }
+
+  #[tokio::test]
+  async fn check_cached_response() {
+    let (url, requests) = serve(vec![
+      "HTTP/1.1 200 OK\r\nCache-Control: max-age=3600\r\nContent-Length: 5\r\nConnection: close\r\n\r\ntauri",
+    ]);
+    let dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new().cache_dir(dir.path()).build().unwrap();
+
+    for from_cache in &[false, true] {
+      let request = HttpRequestBuilder::new("GET", &url).response_type(ResponseType::Text);
+      let response = client.send(request).await.unwrap();
+      assert_eq!(response.from_cache(), *from_cache);
+      let data = serde_json::to_value(response.read().await.unwrap()).unwrap();
+      assert_eq!(data["data"], "tauri");
+      assert_eq!(data["fromCache"], *from_cache);
+    }
+    assert_eq!(requests.lock().unwrap().len(), 1);
+  }
+
+  #[tokio::test]
+  async fn check_cache_write_failure() {
+    let (url, _) = serve(vec![
+      "HTTP/1.1 200 OK\r\nCache-Control: max-age=3600\r\nContent-Length: 5\r\nConnection: close\r\n\r\ntauri",
+    ]);
+    // the cache directory can't be created over a file
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let client = ClientBuilder::new().cache_dir(file.path()).build().unwrap();
+    let request = HttpRequestBuilder::new("GET", &url).response_type(ResponseType::Text);
+    let response = client.send(request).await.unwrap();
+    assert_eq!(
+      serde_json::to_value(response.read().await.unwrap()).unwrap()["data"],
+      "tauri"
+    );
+  }
+
+  #[tokio::test]
+  async fn check_cache_vary_and_credentials() {
+    let response = "HTTP/1.1 200 OK\r\nCache-Control: max-age=3600\r\nVary: Accept-Language\r\nContent-Length: 5\r\nConnection: close\r\n\r\ntauri";
+    let (url, requests) = serve(vec![response, response, response]);
+    let dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new().cache_dir(dir.path()).build().unwrap();
+
+    let send = |headers: Vec<(&str, &str)>| {
+      let headers = headers
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+      client.send(HttpRequestBuilder::new("GET", &url).headers(headers))
+    };
+    assert!(!send(vec![("Accept-Language", "en")])
+      .await
+      .unwrap()
+      .from_cache());
+    assert!(send(vec![("Accept-Language", "en")])
+      .await
+      .unwrap()
+      .from_cache());
+    // other values of the headers named by `Vary` don't match the cached response
+    assert!(!send(vec![("Accept-Language", "fr")])
+      .await
+      .unwrap()
+      .from_cache());
+    // private responses aren't used by requests carrying credentials
+    let authorized = vec![("Accept-Language", "fr"), ("Authorization", "Bearer tauri")];
+    assert!(!send(authorized).await.unwrap().from_cache());
+    assert_eq!(requests.lock().unwrap().len(), 3);
+  }
+
+  #[tokio::test]
+  async fn check_bytes_stream() {
+    let (url, _) = serve(vec![
+      "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n3\r\ntau\r\n2\r\nri\r\n0\r\n\r\n",
+    ]);
+    let client = ClientBuilder::new().build().unwrap();
+    let response = client
+      .send(HttpRequestBuilder::new("GET", &url))
+      .await
+      .unwrap();
+    assert_eq!(response.head().unwrap().status, 200);
+
+    let body = response
+      .bytes_stream()
+      .try_fold(Vec::new(), |mut body, chunk| async move {
+        body.extend_from_slice(&chunk);
+        Ok(body)
+      })
+      .await
+      .unwrap();
+    assert_eq!(body, b"tauri");
+  }
+
+  #[tokio::test]
+  async fn check_revalidated_response() {
+    let (url, requests) = serve(vec![
+      "HTTP/1.1 200 OK\r\nCache-Control: no-cache\r\nETag: \"v1\"\r\nContent-Length: 5\r\nConnection: close\r\n\r\ntauri",
+      "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n",
+    ]);
+    let dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new().cache_dir(dir.path()).build().unwrap();
+
+    for from_cache in &[false, true] {
+      let request = HttpRequestBuilder::new("GET", &url).response_type(ResponseType::Text);
+      let response = client.send(request).await.unwrap();
+      assert_eq!(response.from_cache(), *from_cache);
+      assert_eq!(
+        serde_json::to_value(response.read().await.unwrap()).unwrap()["data"],
+        "tauri"
+      );
+    }
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(!requests[0].contains("if-none-match"));
+    assert!(requests[1].contains("if-none-match: \"v1\""));
+  }
+
+  #[tokio::test]
+  async fn check_cancel_stalled_download() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/file", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut line = String::new();
+      let mut reader = BufReader::new(&mut stream);
+      while reader.read_line(&mut line).unwrap() > 2 {
+        line.clear();
+      }
+      stream
+        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\ntauri")
+        .unwrap();
+      // the transfer stalls with the connection open
+      std::thread::sleep(std::time::Duration::from_secs(10));
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.txt");
+    let client = ClientBuilder::new().build().unwrap();
+    let handle = DownloadHandle::new();
+
+    let handle_ = handle.clone();
+    tokio::spawn(async move {
+      tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+      handle_.cancel();
+    });
+    let status = client
+      .download(DownloadRequestBuilder::new(&url, &path), &handle, |_| {})
+      .await
+      .unwrap();
+    assert_eq!(status, DownloadStatus::Cancelled);
+    assert!(!path.exists());
+  }
+
+  #[tokio::test]
+  async fn check_resume_download() {
+    let (url, requests) = serve(vec![
+      "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */5\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+      "HTTP/1.1 200 OK\r\nETag: \"v2\"\r\nContent-Length: 5\r\nConnection: close\r\n\r\ntauri",
+      "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 3-4/5\r\nContent-Length: 2\r\nConnection: close\r\n\r\nri",
+    ]);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.txt");
+    let validator_path = dir.path().join("file.txt.resume");
+    let client = ClientBuilder::new().build().unwrap();
+
+    // the partial file is longer than the remote file, so the download starts over
+    std::fs::write(&path, "tauri-app").unwrap();
+    std::fs::write(&validator_path, "\"v1\"").unwrap();
+    let status = client
+      .download(
+        DownloadRequestBuilder::new(&url, &path),
+        &DownloadHandle::new(),
+        |_| {},
+      )
+      .await
+      .unwrap();
+    assert_eq!(status, DownloadStatus::Completed);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "tauri");
+    assert!(!validator_path.exists());
+
+    std::fs::write(&path, "tau").unwrap();
+    std::fs::write(&validator_path, "\"v2\"").unwrap();
+    let status = client
+      .download(
+        DownloadRequestBuilder::new(&url, &path),
+        &DownloadHandle::new(),
+        |_| {},
+      )
+      .await
+      .unwrap();
+    assert_eq!(status, DownloadStatus::Completed);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "tauri");
+
+    let requests = requests.lock().unwrap();
+    assert!(requests[0].contains("range: bytes=9-") && requests[0].contains("if-range: \"v1\""));
+    assert!(!requests[1].contains("range:"));
+    assert!(requests[2].contains("range: bytes=3-") && requests[2].contains("if-range: \"v2\""));
+  }
+
+  #[test]
+  fn check_form_part_deserialization() {
+    let text: FormPart = serde_json::from_value(json!("/home/user/file.txt")).unwrap();
+    assert!(matches!(text, FormPart::Text(text) if text == "/home/user/file.txt"));
+
+    let file: FormPart = serde_json::from_value(json!({
+      "file": "/home/user/file.txt",
+      "mime": "text/plain"
+    }))
+    .unwrap();
+    assert!(matches!(
+      file,
+      FormPart::File { mime: Some(mime), file_name: None, .. } if mime == "text/plain"
+    ));
+
+    let bytes: FormPart = serde_json::from_value(json!({
+      "bytes": [1, 2, 3],
+      "fileName": "data.bin"
+    }))
+    .unwrap();
+    assert!(matches!(
+      bytes,
+      FormPart::Bytes { bytes, file_name: Some(file_name), .. }
+        if bytes == vec![1, 2, 3] && file_name == "data.bin"
+    ));
+
+    let form: FormBody =
+      serde_json::from_value(json!([["b", "first"], ["a", { "file": "file.txt" }]])).unwrap();
+    assert!(form.has_files());
+    assert_eq!(
+      form
+        .0
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>(),
+      vec!["b", "a"]
+    );
+  }
+
+  #[test]
+  fn check_verify_checksum() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.txt");
+    std::fs::write(&path, "tauri").unwrap();
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"tauri");
+    let checksum = hex::encode(hasher.clone().finalize());
+
+    let request = DownloadRequestBuilder::new("http://localhost", &path).sha256(checksum);
+    assert!(verify_checksum(&request, hasher.clone()).is_ok());
+    assert!(path.exists());
+
+    let request = DownloadRequestBuilder::new("http://localhost", &path).sha256("00");
+    assert!(matches!(
+      verify_checksum(&request, hasher),
+      Err(crate::Error::ChecksumMismatch { .. })
+    ));
+    assert!(!path.exists());
+  }
 }
@@ -0,0 +1,271 @@
+use reqwest::{
+  header::{HeaderMap, HeaderName, HeaderValue, CACHE_CONTROL, ETAG, EXPIRES, LAST_MODIFIED, VARY},
+  Url,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A cached response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CacheEntry {
+  /// The response URL.
+  pub(super) url: String,
+  /// The response status code.
+  pub(super) status: u16,
+  /// The response headers.
+  pub(super) headers: Vec<(String, String)>,
+  /// The time, in seconds since the Unix epoch, when the response stops being fresh.
+  expires_at: u64,
+  /// The request headers named by the `Vary` response header and their values.
+  #[serde(default)]
+  vary: Vec<(String, Option<String>)>,
+  /// Whether the response is marked as `public` and can be shared by requests carrying credentials.
+  #[serde(default)]
+  public: bool,
+}
+
+impl CacheEntry {
+  /// Creates the cache entry of a response to the given request headers,
+  /// returning `None` if the response must not be stored.
+  pub(super) fn new(
+    url: &Url,
+    status: u16,
+    headers: &HeaderMap,
+    request_headers: &HeaderMap,
+  ) -> Option<Self> {
+    let mut entry = Self {
+      url: url.to_string(),
+      status,
+      headers: Vec::new(),
+      expires_at: 0,
+      vary: Vec::new(),
+      public: false,
+    };
+    entry.update(headers)?;
+    if let Some(vary) = header(headers, &VARY) {
+      entry.vary = vary
+        .split(',')
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+          let value = request_header(request_headers, &name);
+          (name, value)
+        })
+        .collect();
+    }
+    // a stale response is only useful if it can be revalidated
+    if entry.expires_at <= now() && entry.etag().is_none() && entry.last_modified().is_none() {
+      return None;
+    }
+    Some(entry)
+  }
+
+  /// Updates the headers and the freshness with the ones of a response.
+  /// Returns `None` if the response must not be stored.
+  pub(super) fn update(&mut self, headers: &HeaderMap) -> Option<()> {
+    if header(headers, &VARY).map(str::trim) == Some("*") {
+      return None;
+    }
+
+    let mut max_age = None;
+    let mut public = false;
+    if let Some(cache_control) = header(headers, &CACHE_CONTROL) {
+      for directive in cache_control.split(',') {
+        let directive = directive.trim().to_lowercase();
+        if directive == "no-store" {
+          return None;
+        } else if directive == "public" {
+          public = true;
+        } else if directive == "no-cache" {
+          max_age = Some(0);
+        } else if let Some(seconds) = directive.strip_prefix("max-age=") {
+          max_age = max_age.or_else(|| seconds.trim_matches('"').parse::<u64>().ok());
+        }
+      }
+    }
+    self.expires_at = match max_age {
+      Some(max_age) => now() + max_age,
+      // an invalid date means the response is already expired
+      None => header(headers, &EXPIRES)
+        .map(|expires| {
+          httpdate::parse_http_date(expires)
+            .ok()
+            .and_then(|expires| expires.duration_since(UNIX_EPOCH).ok())
+            .map(|expires| expires.as_secs())
+            .unwrap_or_default()
+        })
+        .unwrap_or_default(),
+    };
+    self.public = public;
+
+    for (name, value) in headers {
+      if let Ok(value) = value.to_str() {
+        self.headers.retain(|(n, _)| n != name.as_str());
+        self
+          .headers
+          .push((name.as_str().to_string(), value.to_string()));
+      }
+    }
+    Some(())
+  }
+
+  /// Whether the response was stored for a request with the same values of the headers named by `Vary`.
+  pub(super) fn matches(&self, request_headers: &HeaderMap) -> bool {
+    self
+      .vary
+      .iter()
+      .all(|(name, value)| &request_header(request_headers, name) == value)
+  }
+
+  /// Whether the response can be shared by requests carrying credentials.
+  pub(super) fn is_public(&self) -> bool {
+    self.public
+  }
+
+  /// Whether the response can be used without revalidating it.
+  pub(super) fn is_fresh(&self) -> bool {
+    now() < self.expires_at
+  }
+
+  /// The entity tag of the response.
+  pub(super) fn etag(&self) -> Option<&str> {
+    self.header(ETAG.as_str())
+  }
+
+  /// The last modification date of the response.
+  pub(super) fn last_modified(&self) -> Option<&str> {
+    self.header(LAST_MODIFIED.as_str())
+  }
+
+  /// The response headers.
+  pub(super) fn header_map(&self) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &self.headers {
+      if let (Ok(name), Ok(value)) = (
+        HeaderName::from_bytes(name.as_bytes()),
+        HeaderValue::from_str(value),
+      ) {
+        headers.append(name, value);
+      }
+    }
+    headers
+  }
+
+  fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(n, _)| n == name)
+      .map(|(_, value)| value.as_str())
+  }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Option<&'a str> {
+  headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// Gets all the values of a request header, joined by commas.
+fn request_header(headers: &HeaderMap, name: &str) -> Option<String> {
+  let values = headers
+    .get_all(name)
+    .iter()
+    .filter_map(|value| value.to_str().ok())
+    .collect::<Vec<&str>>();
+  if values.is_empty() {
+    None
+  } else {
+    Some(values.join(", "))
+  }
+}
+
+fn now() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|now| now.as_secs())
+    .unwrap_or_default()
+}
+
+/// An on-disk cache of HTTP responses, keyed by URL and the request headers named by `Vary`.
+///
+/// Each URL has a single entry, stored as a `{key}.json` metadata file and a `{key}.body` file.
+/// An entry stored for other values of the headers named by `Vary` is a cache miss and is replaced by the new response.
+#[derive(Debug, Clone)]
+pub(super) struct HttpCache {
+  dir: PathBuf,
+}
+
+impl HttpCache {
+  pub(super) fn new(dir: PathBuf) -> Self {
+    Self { dir }
+  }
+
+  /// The default cache directory of a client: `{cache_dir}/{app_name}/http/{partition}`.
+  ///
+  /// The partition is derived from the client credentials, so clients with different cookie jars
+  /// or client certificates don't share their cached responses.
+  pub(super) fn default_dir(
+    cookie_jar: Option<&str>,
+    client_certificate: Option<&Path>,
+  ) -> crate::Result<PathBuf> {
+    let mut partition = Sha256::new();
+    partition.update(cookie_jar.unwrap_or_default().as_bytes());
+    partition.update([0u8]);
+    if let Some(client_certificate) = client_certificate {
+      partition.update(client_certificate.to_string_lossy().as_bytes());
+    }
+    crate::path::resolve_path(
+      PathBuf::from(crate::path::app_name()?)
+        .join("http")
+        .join(hex::encode(partition.finalize())),
+      Some(crate::path::BaseDirectory::Cache),
+    )
+  }
+
+  fn key(url: &Url) -> String {
+    hex::encode(Sha256::digest(url.as_str().as_bytes()))
+  }
+
+  /// Gets the cached response of the URL for the given request headers.
+  pub(super) fn get(
+    &self,
+    url: &Url,
+    request_headers: &HeaderMap,
+  ) -> Option<(CacheEntry, Vec<u8>)> {
+    let key = Self::key(url);
+    let entry = fs::read(self.dir.join(format!("{}.json", key))).ok()?;
+    let entry: CacheEntry = serde_json::from_slice(&entry).ok()?;
+    if !entry.matches(request_headers) {
+      return None;
+    }
+    let body = fs::read(self.dir.join(format!("{}.body", key))).ok()?;
+    Some((entry, body))
+  }
+
+  /// Stores the response of the URL. The body is only written if given.
+  ///
+  /// The body is written before the metadata, so a failed write never pairs a new body with the previous metadata.
+  pub(super) fn put(
+    &self,
+    url: &Url,
+    entry: &CacheEntry,
+    body: Option<&[u8]>,
+  ) -> crate::Result<()> {
+    let key = Self::key(url);
+    let metadata_path = self.dir.join(format!("{}.json", key));
+    fs::create_dir_all(&self.dir)?;
+    if let Some(body) = body {
+      // the previous metadata doesn't describe the new body
+      if metadata_path.exists() {
+        fs::remove_file(&metadata_path)?;
+      }
+      crate::file::write_atomic(self.dir.join(format!("{}.body", key)), body)?;
+    }
+    crate::file::write_atomic(metadata_path, serde_json::to_vec(entry)?)
+  }
+}
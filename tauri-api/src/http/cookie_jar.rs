@@ -0,0 +1,143 @@
+use cookie_store::CookieStore;
+use once_cell::sync::Lazy;
+use reqwest::{header::HeaderValue, Url};
+
+use std::{
+  collections::HashMap,
+  fs::{self, File},
+  io::BufReader,
+  path::PathBuf,
+  sync::{Arc, Mutex},
+};
+
+/// A cookie store persisted to a JSON file.
+///
+/// The file is rewritten every time the server sets cookies. Session cookies and expired cookies aren't persisted.
+pub struct CookieJar {
+  path: PathBuf,
+  store: Mutex<CookieStore>,
+}
+
+impl CookieJar {
+  /// Loads the cookie jar from the given file, or creates an empty jar if the file doesn't exist.
+  pub fn load(path: impl Into<PathBuf>) -> crate::Result<Self> {
+    let path = path.into();
+    let store = if path.exists() {
+      CookieStore::load_json(BufReader::new(File::open(&path)?))
+        .map_err(|e| crate::Error::CookieJar(e.to_string()))?
+    } else {
+      CookieStore::default()
+    };
+    Ok(Self {
+      path,
+      store: Mutex::new(store),
+    })
+  }
+
+  /// Loads the cookie jar with the given name, persisted to `{app_dir}/cookies/{name}.json`.
+  ///
+  /// Clients using the same name share the same jar.
+  pub fn named(name: &str) -> crate::Result<Arc<Self>> {
+    static JARS: Lazy<Mutex<HashMap<String, Arc<CookieJar>>>> = Lazy::new(Default::default);
+
+    if name.is_empty()
+      || !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+      return Err(crate::Error::CookieJar(format!(
+        "invalid cookie jar name `{}`",
+        name
+      )));
+    }
+
+    let mut jars = JARS.lock().expect("Failed to lock cookie jars: named()");
+    if let Some(jar) = jars.get(name) {
+      return Ok(jar.clone());
+    }
+    let path = crate::path::app_dir()
+      .ok_or_else(|| crate::Error::CookieJar("failed to resolve the app directory".into()))?
+      .join("cookies")
+      .join(format!("{}.json", name));
+    let jar = Arc::new(Self::load(path)?);
+    jars.insert(name.to_string(), jar.clone());
+    Ok(jar)
+  }
+
+  fn save(&self, store: &CookieStore) -> crate::Result<()> {
+    let mut contents = Vec::new();
+    store
+      .save_json(&mut contents)
+      .map_err(|e| crate::Error::CookieJar(e.to_string()))?;
+    if let Some(parent) = self.path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    crate::file::write_atomic(&self.path, contents)
+  }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+  fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+    let mut store = self
+      .store
+      .lock()
+      .expect("Failed to lock cookie jar: set_cookies()");
+    for header in cookie_headers {
+      if let Ok(cookie) = header.to_str() {
+        let _ = store.parse(cookie, url);
+      }
+    }
+    // the cookies are still available in memory if the file can't be written
+    let _ = self.save(&store);
+  }
+
+  fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+    let store = self
+      .store
+      .lock()
+      .expect("Failed to lock cookie jar: cookies()");
+    let cookies = store
+      .get_request_cookies(url)
+      .map(|cookie| {
+        let (name, value) = cookie.name_value();
+        format!("{}={}", name, value)
+      })
+      .collect::<Vec<String>>()
+      .join("; ");
+    if cookies.is_empty() {
+      None
+    } else {
+      HeaderValue::from_str(&cookies).ok()
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::CookieJar;
+  use reqwest::{cookie::CookieStore, header::HeaderValue};
+
+  #[test]
+  fn check_persisted_cookies() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cookies.json");
+    let url = "https://tauri.studio/docs".parse().unwrap();
+
+    let jar = CookieJar::load(&path).unwrap();
+    let headers = [
+      HeaderValue::from_static("persisted=1; Max-Age=3600"),
+      HeaderValue::from_static("session=2"),
+    ];
+    jar.set_cookies(&mut headers.iter(), &url);
+    assert!(jar.cookies(&url).is_some());
+
+    // session cookies aren't persisted
+    let jar = CookieJar::load(&path).unwrap();
+    assert_eq!(
+      jar.cookies(&url),
+      Some(HeaderValue::from_static("persisted=1"))
+    );
+
+    assert!(CookieJar::named("../escape").is_err());
+  }
+}
@@ -0,0 +1,138 @@
+use reqwest::Url;
+
+/// A URL pattern of the HTTP scope.
+#[derive(Debug, Clone, PartialEq)]
+struct ScopePattern {
+  /// The URL scheme.
+  scheme: String,
+  /// The host glob, including the port if the pattern has one.
+  host: String,
+  /// The path prefix.
+  path: String,
+}
+
+impl ScopePattern {
+  fn parse(pattern: &str) -> crate::Result<Self> {
+    let invalid = || crate::Error::InvalidScopePattern(pattern.to_string());
+    let (scheme, rest) = split_once(pattern, "://").ok_or_else(invalid)?;
+    let (host, path) = match rest.find('/') {
+      Some(index) => (&rest[..index], &rest[index..]),
+      None => (rest, "/"),
+    };
+    if scheme.is_empty() || host.is_empty() {
+      return Err(invalid());
+    }
+    Ok(Self {
+      scheme: scheme.to_lowercase(),
+      host: host.to_lowercase(),
+      path: path.to_string(),
+    })
+  }
+
+  fn matches(&self, url: &Url) -> bool {
+    let host = match (url.host_str(), url.port()) {
+      (Some(host), Some(port)) => format!("{}:{}", host, port),
+      (Some(host), None) => host.to_string(),
+      (None, _) => return false,
+    };
+    let path = url.path();
+    self.scheme == url.scheme()
+      && glob_matches(&self.host, &host)
+      && (path == self.path
+        || (path.starts_with(&self.path)
+          && (self.path.ends_with('/') || path[self.path.len()..].starts_with('/'))))
+  }
+}
+
+/// Splits the string on the first occurrence of the delimiter.
+fn split_once<'a>(value: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+  value
+    .find(delimiter)
+    .map(|index| (&value[..index], &value[index + delimiter.len()..]))
+}
+
+/// Checks if the value matches the glob, where `*` matches any sequence of characters.
+fn glob_matches(glob: &str, value: &str) -> bool {
+  let mut parts = glob.split('*');
+  // the glob always has at least one part
+  let first = parts.next().unwrap_or_default();
+  if !value.starts_with(first) {
+    return false;
+  }
+  let mut remaining = &value[first.len()..];
+  let mut parts = parts.peekable();
+  while let Some(part) = parts.next() {
+    if parts.peek().is_none() {
+      // the last part must match the end of the value
+      return remaining.ends_with(part);
+    }
+    match remaining.find(part) {
+      Some(index) => remaining = &remaining[index + part.len()..],
+      None => return false,
+    }
+  }
+  // the glob has no wildcard
+  remaining.is_empty()
+}
+
+/// The set of URLs an HTTP client is allowed to access.
+///
+/// # Examples
+/// ```
+/// use tauri_api::http::Scope;
+/// let scope = Scope::new(&["https://*.tauri.studio/api/"]).unwrap();
+/// assert!(scope.is_allowed(&"https://docs.tauri.studio/api/config".parse().unwrap()));
+/// assert!(!scope.is_allowed(&"http://docs.tauri.studio/api/config".parse().unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scope(Vec<ScopePattern>);
+
+impl Scope {
+  /// Creates a scope from URL patterns, e.g. `https://*.tauri.studio/api/`.
+  ///
+  /// A pattern is made of a scheme, a host glob where `*` matches any sequence of characters and a path prefix.
+  pub fn new<S: AsRef<str>>(patterns: &[S]) -> crate::Result<Self> {
+    patterns
+      .iter()
+      .map(|pattern| ScopePattern::parse(pattern.as_ref()))
+      .collect::<crate::Result<Vec<ScopePattern>>>()
+      .map(Self)
+  }
+
+  /// Checks if the URL matches one of the scope patterns.
+  pub fn is_allowed(&self, url: &Url) -> bool {
+    self.0.iter().any(|pattern| pattern.matches(url))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{glob_matches, Scope};
+
+  #[test]
+  fn check_glob_matches() {
+    assert!(glob_matches("tauri.studio", "tauri.studio"));
+    assert!(!glob_matches("tauri.studio", "docs.tauri.studio"));
+    assert!(glob_matches("*.tauri.studio", "docs.tauri.studio"));
+    assert!(!glob_matches("*.tauri.studio", "tauri.studio.evil.com"));
+    assert!(glob_matches("localhost:*", "localhost:8080"));
+    assert!(glob_matches("*", "tauri.studio"));
+    assert!(glob_matches("api.*.tauri.*", "api.v1.tauri.studio"));
+  }
+
+  #[test]
+  fn check_scope() {
+    let scope = Scope::new(&["https://*.tauri.studio/api", "http://localhost:*"]).unwrap();
+    let allowed = |url: &str| scope.is_allowed(&url.parse().unwrap());
+
+    assert!(allowed("https://docs.tauri.studio/api"));
+    assert!(allowed("https://docs.tauri.studio/api/config?q=1"));
+    assert!(!allowed("https://docs.tauri.studio/apis"));
+    assert!(!allowed("https://docs.tauri.studio/api/../secret"));
+    assert!(!allowed("http://docs.tauri.studio/api"));
+    assert!(allowed("http://localhost:8080/"));
+    assert!(!allowed("http://localhost/"));
+
+    assert!(Scope::new(&["tauri.studio"]).is_err());
+  }
+}
@@ -15,6 +15,10 @@ pub mod http;
 pub mod path;
 /// The RPC module includes utilities to send messages to the JS layer of the webview.
 pub mod rpc;
+/// The Shell API module opens files and URLs with the default or a specified application.
+pub mod shell;
+/// The Sidecar API module manages the external binaries bundled with the app.
+pub mod sidecar;
 /// TCP ports access API.
 pub mod tcp;
 /// The semver API.
@@ -38,6 +42,18 @@ pub mod shortcuts;
 #[cfg(feature = "notification")]
 pub mod notification;
 
+/// The clipboard API module allows you to read and write plain text.
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+
+/// The WebSocket client API.
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+/// The file system watcher API.
+#[cfg(feature = "fs-watch")]
+pub mod watcher;
+
 pub use tauri_utils::*;
 
 mod error;
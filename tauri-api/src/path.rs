@@ -180,7 +180,7 @@ pub fn resource_dir() -> Option<PathBuf> {
   crate::platform::resource_dir().ok()
 }
 
-fn app_name() -> crate::Result<String> {
+pub(crate) fn app_name() -> crate::Result<String> {
   let exe = std::env::current_exe()?;
   let app_name = exe
     .file_name()
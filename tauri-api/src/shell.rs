@@ -0,0 +1,318 @@
+use reqwest::Url;
+use serde::Deserialize;
+use tokio::process::Command;
+
+#[cfg(not(any(target_os = "macos", windows)))]
+use std::io::ErrorKind;
+use std::{path::Path, process::Stdio, time::Duration};
+
+/// The time an opener is given to report a failure.
+/// An opener still running after it, e.g. a browser started by the call, is left running.
+const OPENER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A program used to open files and URLs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Program {
+  /// The macOS `open` command.
+  Open,
+  /// The Windows file explorer, opening the path with its default application.
+  ///
+  /// Its failures aren't reported, since its exit code doesn't reflect them.
+  Explorer,
+  /// The Linux `xdg-open` command.
+  XdgOpen,
+  /// The Linux `gio open` command.
+  Gio,
+  /// The Linux `gnome-open` command.
+  GnomeOpen,
+  /// The Linux `kde-open` command.
+  KdeOpen,
+  /// The WSL `wslview` command.
+  #[serde(rename = "wslview")]
+  WslView,
+  /// The Firefox browser.
+  Firefox,
+  /// The Google Chrome browser.
+  GoogleChrome,
+  /// The Chromium browser.
+  Chromium,
+  /// The Safari browser.
+  Safari,
+}
+
+impl Program {
+  /// Opens the path or URL with this program.
+  async fn open(self, path: &str) -> crate::Result<()> {
+    match self {
+      Self::Open => run("open", &[path]).await,
+      Self::Explorer => spawn("explorer", &[path]).map(|_| ()),
+      Self::XdgOpen => run("xdg-open", &[path]).await,
+      Self::Gio => run("gio", &["open", path]).await,
+      Self::GnomeOpen => run("gnome-open", &[path]).await,
+      Self::KdeOpen => run("kde-open", &[path]).await,
+      Self::WslView => run("wslview", &[path]).await,
+      Self::Firefox => browser("Firefox", "firefox", path).await,
+      Self::GoogleChrome => browser("Google Chrome", "chrome", path).await,
+      Self::Chromium => browser("Chromium", "chromium", path).await,
+      Self::Safari => run("open", &["-a", "Safari", path]).await,
+    }
+  }
+}
+
+fn command(program: &str, args: &[&str]) -> Command {
+  let mut command = Command::new(program);
+  command
+    .args(args)
+    .stdin(Stdio::null())
+    .stdout(Stdio::null())
+    .stderr(Stdio::null());
+  #[cfg(windows)]
+  command.creation_flags(crate::command::CREATE_NO_WINDOW);
+  command
+}
+
+/// Spawns the program without waiting for it to finish.
+fn spawn(program: &str, args: &[&str]) -> crate::Result<tokio::process::Child> {
+  command(program, args).spawn().map_err(Into::into)
+}
+
+/// Runs the program, returning an error if it fails within the `OPENER_TIMEOUT`.
+async fn run(program: &str, args: &[&str]) -> crate::Result<()> {
+  let mut child = spawn(program, args)?;
+  match tokio::time::timeout(OPENER_TIMEOUT, child.wait()).await {
+    Ok(status) => {
+      let status = status?;
+      if status.success() {
+        Ok(())
+      } else {
+        Err(crate::Error::Command(format!(
+          "{} exited with {}",
+          program, status
+        )))
+      }
+    }
+    // the runtime reaps the process once it exits
+    Err(_) => Ok(()),
+  }
+}
+
+/// Opens the file or runs the program with the parameters with `ShellExecuteW`,
+/// which finds the executables in the `App Paths` registry key.
+#[cfg(windows)]
+fn shell_execute(file: &str, parameters: Option<&str>) -> crate::Result<()> {
+  use std::{ffi::OsStr, iter::once, os::windows::ffi::OsStrExt, ptr};
+  use winapi::um::{shellapi::ShellExecuteW, winuser::SW_SHOWNORMAL};
+
+  fn wide(value: &str) -> Vec<u16> {
+    OsStr::new(value).encode_wide().chain(once(0)).collect()
+  }
+
+  let verb = wide("open");
+  let file = wide(file);
+  let parameters = parameters.map(wide);
+  let instance = unsafe {
+    ShellExecuteW(
+      ptr::null_mut(),
+      verb.as_ptr(),
+      file.as_ptr(),
+      parameters
+        .as_ref()
+        .map_or(ptr::null(), |parameters| parameters.as_ptr()),
+      ptr::null(),
+      SW_SHOWNORMAL,
+    )
+  };
+  // values greater than 32 mean success
+  if instance as isize > 32 {
+    Ok(())
+  } else {
+    Err(std::io::Error::last_os_error().into())
+  }
+}
+
+#[cfg(target_os = "macos")]
+async fn browser(app_name: &str, _executable: &str, path: &str) -> crate::Result<()> {
+  run("open", &["-a", app_name, path]).await
+}
+
+#[cfg(windows)]
+async fn browser(_app_name: &str, executable: &str, path: &str) -> crate::Result<()> {
+  shell_execute(
+    &format!("{}.exe", executable),
+    Some(&quote_parameter(path)?),
+  )
+}
+
+/// Quotes the path or URL as a single command line parameter.
+///
+/// URLs are normalized, which percent-encodes their quotes.
+/// The paths can't contain quotes on Windows, so the remaining ones are rejected instead of ending the parameter.
+#[cfg(any(windows, test))]
+fn quote_parameter(path_or_url: &str) -> crate::Result<String> {
+  let parameter = match Url::parse(path_or_url) {
+    // single letter schemes are Windows drives
+    Ok(url) if url.scheme().len() > 1 => url.as_str().to_string(),
+    _ => path_or_url.to_string(),
+  };
+  if parameter.contains('"') {
+    Err(crate::Error::InvalidUrl(path_or_url.to_string()))
+  } else {
+    Ok(format!("\"{}\"", parameter))
+  }
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+async fn browser(_app_name: &str, executable: &str, path: &str) -> crate::Result<()> {
+  let executable = if executable == "chrome" {
+    "google-chrome"
+  } else {
+    executable
+  };
+  run(executable, &[path]).await
+}
+
+/// Opens the path or URL with the given program, or with the default one for its type.
+///
+/// On Linux, the default program is `xdg-open`, or `gio open` if it isn't installed.
+/// On Windows, the path is opened with `ShellExecuteW`.
+///
+/// The opener failures, e.g. a path without an associated application, are returned as errors.
+///
+/// # Examples
+/// ```no_run
+/// use tauri_api::shell::{open, Program};
+///
+/// async fn run() {
+///   // opens the PDF in the system viewer
+///   open("/home/user/report.pdf", None).await.unwrap();
+///   // opens the website in Firefox
+///   open("https://tauri.studio", Some(Program::Firefox))
+///     .await
+///     .unwrap();
+/// }
+/// ```
+pub async fn open(path: impl AsRef<str>, with: Option<Program>) -> crate::Result<()> {
+  let path = path.as_ref();
+  if let Some(program) = with {
+    return program.open(path).await;
+  }
+
+  #[cfg(target_os = "macos")]
+  return Program::Open.open(path).await;
+  #[cfg(windows)]
+  return shell_execute(path, None);
+  #[cfg(not(any(target_os = "macos", windows)))]
+  match Program::XdgOpen.open(path).await {
+    Err(crate::Error::Io(e)) if e.kind() == ErrorKind::NotFound => Program::Gio.open(path).await,
+    result => result,
+  }
+}
+
+/// Shows the file or directory in the file manager, selecting it.
+///
+/// On Linux, the parent directory is opened if the file manager doesn't implement the `org.freedesktop.FileManager1` D-Bus interface.
+pub async fn reveal(path: impl AsRef<Path>) -> crate::Result<()> {
+  let path = path.as_ref().canonicalize()?;
+
+  #[cfg(target_os = "macos")]
+  return run("open", &["-R", &path.to_string_lossy()]).await;
+  #[cfg(windows)]
+  {
+    // the file explorer doesn't support the verbatim paths returned by `canonicalize`
+    let path = path.display().to_string();
+    let path = path.trim_start_matches(r"\\?\");
+    spawn("explorer", &[&format!("/select,{}", path)]).map(|_| ())
+  }
+  #[cfg(not(any(target_os = "macos", windows)))]
+  {
+    let uri = Url::from_file_path(&path)
+      .map_err(|_| crate::Error::Path(format!("invalid path {}", path.display())))?;
+    let revealed = run(
+      "dbus-send",
+      &[
+        "--session",
+        "--dest=org.freedesktop.FileManager1",
+        "--type=method_call",
+        "/org/freedesktop/FileManager1",
+        "org.freedesktop.FileManager1.ShowItems",
+        &format!("array:string:{}", uri),
+        "string:",
+      ],
+    )
+    .await
+    .is_ok();
+    if revealed {
+      Ok(())
+    } else {
+      let dir = path.parent().unwrap_or(&path);
+      open(dir.to_string_lossy(), None).await
+    }
+  }
+}
+
+/// Checks that the scheme of the URL is one of the given schemes.
+///
+/// Paths are checked as `file` URLs, so they're only allowed if the `file` scheme is.
+pub fn check_scheme(path_or_url: &str, schemes: &[String]) -> crate::Result<()> {
+  let scheme = match Url::parse(path_or_url) {
+    // single letter schemes are Windows drives
+    Ok(url) if url.scheme().len() > 1 => url.scheme().to_string(),
+    _ => "file".to_string(),
+  };
+  if schemes
+    .iter()
+    .any(|allowed| allowed.eq_ignore_ascii_case(&scheme))
+  {
+    Ok(())
+  } else {
+    Err(crate::Error::SchemeNotAllowed(scheme))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  #[cfg(unix)]
+  use super::run;
+  use super::{check_scheme, quote_parameter};
+
+  #[test]
+  fn check_open_schemes() {
+    let schemes = vec!["https".to_string(), "mailto".to_string()];
+    assert!(check_scheme("https://tauri.studio", &schemes).is_ok());
+    assert!(check_scheme("mailto:hello@tauri.studio", &schemes).is_ok());
+    assert!(check_scheme("http://tauri.studio", &schemes).is_err());
+    assert!(check_scheme("javascript:alert(1)", &schemes).is_err());
+    assert!(check_scheme("/usr/bin/env", &schemes).is_err());
+    assert!(check_scheme("C:\\Windows\\notepad.exe", &schemes).is_err());
+    assert!(check_scheme("file:", &schemes).is_err());
+
+    let schemes = vec!["file".to_string()];
+    assert!(check_scheme("/home/user/report.pdf", &schemes).is_ok());
+    assert!(check_scheme("file:///home/user/report.pdf", &schemes).is_ok());
+    assert!(check_scheme("file:", &schemes).is_ok());
+  }
+
+  #[test]
+  fn check_quote_parameter() {
+    assert_eq!(
+      quote_parameter("https://tauri.studio/?q=\"a\" --incognito").unwrap(),
+      "\"https://tauri.studio/?q=%22a%22%20--incognito\""
+    );
+    assert_eq!(
+      quote_parameter("C:\\Users\\report.pdf").unwrap(),
+      "\"C:\\Users\\report.pdf\""
+    );
+    assert!(quote_parameter("C:\\Users\\\" --incognito").is_err());
+  }
+
+  #[cfg(unix)]
+  #[tokio::test]
+  async fn check_opener_failure() {
+    assert!(run("true", &[]).await.is_ok());
+    assert!(run("false", &[]).await.is_err());
+    assert!(run("tauri-missing-opener", &[]).await.is_err());
+    // the opener is left running after the timeout
+    assert!(run("sleep", &["5"]).await.is_ok());
+  }
+}
@@ -0,0 +1,436 @@
+use crate::command::{CommandBuilder, CommandChild, CommandEvent, TerminatedPayload};
+
+use serde::Serialize;
+use tokio::sync::{
+  mpsc::{self, Receiver, Sender},
+  watch,
+};
+
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+  },
+  time::Duration,
+};
+
+/// The number of sidecar events buffered before the supervisor waits for the receiver.
+const EVENT_BUFFER: usize = 32;
+
+/// Resolves the path of a sidecar binary bundled next to the current executable.
+///
+/// The bundler copies the `externalBin` binaries with a target triple suffix,
+/// so `binaries/my-sidecar` resolves to `my-sidecar-x86_64-unknown-linux-gnu` on Linux.
+/// The binary without the suffix is used if it doesn't exist.
+pub fn sidecar_path(name: impl AsRef<Path>) -> crate::Result<PathBuf> {
+  let exe = std::env::current_exe()?;
+  let exe_dir = exe
+    .parent()
+    .ok_or_else(|| crate::Error::Command("Could not evaluate executable dir".to_string()))?;
+  let file_name = name
+    .as_ref()
+    .file_name()
+    .ok_or_else(|| crate::Error::Command(format!("invalid sidecar name {:?}", name.as_ref())))?
+    .to_string_lossy()
+    .into_owned();
+  let suffix = std::env::consts::EXE_SUFFIX;
+
+  let path = exe_dir.join(format!(
+    "{}{}",
+    crate::command::binary_command(file_name.clone())?,
+    suffix
+  ));
+  if path.exists() {
+    return Ok(path);
+  }
+  let path = exe_dir.join(format!("{}{}", file_name, suffix));
+  if path.exists() {
+    Ok(path)
+  } else {
+    Err(crate::Error::Command(format!(
+      "sidecar `{}` not found in {}",
+      file_name,
+      exe_dir.display()
+    )))
+  }
+}
+
+/// When a sidecar is restarted after it terminates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestartPolicy {
+  /// The sidecar is never restarted.
+  Never,
+  /// The sidecar is restarted if it fails or is terminated by a signal.
+  OnFailure {
+    /// The maximum number of restarts.
+    max_restarts: u32,
+    /// The delay before restarting.
+    delay: Duration,
+  },
+  /// The sidecar is always restarted, even if it exits successfully.
+  Always {
+    /// The maximum number of restarts.
+    max_restarts: u32,
+    /// The delay before restarting.
+    delay: Duration,
+  },
+}
+
+impl Default for RestartPolicy {
+  fn default() -> Self {
+    Self::Never
+  }
+}
+
+impl RestartPolicy {
+  /// Gets the restart delay if the sidecar must be restarted after the given termination.
+  /// The status is `None` if waiting for the process failed.
+  fn restart_delay(&self, status: Option<&TerminatedPayload>, restarts: u32) -> Option<Duration> {
+    match self {
+      Self::Never => None,
+      Self::OnFailure {
+        max_restarts,
+        delay,
+      } => {
+        let success = status.map_or(false, |status| status.code == Some(0));
+        if !success && restarts < *max_restarts {
+          Some(*delay)
+        } else {
+          None
+        }
+      }
+      Self::Always {
+        max_restarts,
+        delay,
+      } => {
+        if restarts < *max_restarts {
+          Some(*delay)
+        } else {
+          None
+        }
+      }
+    }
+  }
+}
+
+/// An event of a sidecar.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", content = "payload")]
+pub enum SidecarEvent {
+  /// A line written to the standard output, without its line ending.
+  Stdout(String),
+  /// A line written to the standard error, without its line ending.
+  Stderr(String),
+  /// Reading the output, waiting for the process or restarting it failed.
+  Error(String),
+  /// The process terminated. It's restarted according to the [`RestartPolicy`].
+  Terminated(TerminatedPayload),
+  /// The process was restarted with the given process identifier.
+  Restarted(u32),
+}
+
+impl From<CommandEvent> for SidecarEvent {
+  fn from(event: CommandEvent) -> Self {
+    match event {
+      CommandEvent::Stdout(line) => Self::Stdout(line),
+      CommandEvent::Stderr(line) => Self::Stderr(line),
+      CommandEvent::Error(error) => Self::Error(error),
+      CommandEvent::Terminated(payload) => Self::Terminated(payload),
+    }
+  }
+}
+
+/// A sidecar builder.
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use tauri_api::sidecar::{RestartPolicy, SidecarBuilder, SidecarEvent};
+///
+/// # async fn run() -> tauri_api::Result<()> {
+/// let (mut events, sidecar) = SidecarBuilder::new("binaries/server")
+///   .args(vec!["--port".to_string(), "8080".to_string()])
+///   .restart_policy(RestartPolicy::OnFailure {
+///     max_restarts: 3,
+///     delay: Duration::from_secs(1),
+///   })
+///   .spawn()?;
+/// while let Some(event) = events.recv().await {
+///   if let SidecarEvent::Stdout(line) = event {
+///     println!("server: {}", line);
+///   }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SidecarBuilder {
+  name: String,
+  program: Option<PathBuf>,
+  args: Vec<String>,
+  cwd: Option<PathBuf>,
+  env: HashMap<String, String>,
+  restart_policy: RestartPolicy,
+}
+
+impl SidecarBuilder {
+  /// Creates a builder for the sidecar with the given `externalBin` name, resolved with [`sidecar_path`].
+  pub fn new(name: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      program: None,
+      args: Vec::new(),
+      cwd: None,
+      env: HashMap::new(),
+      restart_policy: RestartPolicy::default(),
+    }
+  }
+
+  /// Sets the path of the binary, instead of resolving it next to the current executable.
+  pub fn program(mut self, program: impl Into<PathBuf>) -> Self {
+    self.program = Some(program.into());
+    self
+  }
+
+  /// Sets the process arguments.
+  pub fn args(mut self, args: Vec<String>) -> Self {
+    self.args = args;
+    self
+  }
+
+  /// Sets the process working directory.
+  pub fn current_dir(mut self, cwd: impl Into<PathBuf>) -> Self {
+    self.cwd = Some(cwd.into());
+    self
+  }
+
+  /// Adds environment variables to the ones inherited from the current process.
+  pub fn envs(mut self, env: HashMap<String, String>) -> Self {
+    self.env.extend(env);
+    self
+  }
+
+  /// Sets when the sidecar is restarted after it terminates.
+  pub fn restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+    self.restart_policy = restart_policy;
+    self
+  }
+
+  /// Spawns the sidecar, returning its events and a handle to write to its stdin or kill it.
+  ///
+  /// The output is always read, even if the events aren't received.
+  /// The events channel is closed once the sidecar terminates and isn't restarted.
+  /// This must be called from a Tokio runtime.
+  pub fn spawn(self) -> crate::Result<(Receiver<SidecarEvent>, Sidecar)> {
+    let program = match &self.program {
+      Some(program) => program.clone(),
+      None => sidecar_path(&self.name)?,
+    };
+    let mut command = CommandBuilder::new(program.to_string_lossy()).args(self.args);
+    if let Some(cwd) = self.cwd {
+      command = command.current_dir(cwd);
+    }
+    let command = command.envs(self.env);
+
+    let (events, child) = command.clone().spawn()?;
+    let (tx, rx) = mpsc::channel(EVENT_BUFFER);
+    let (finished_tx, finished_rx) = watch::channel(false);
+    let sidecar = Sidecar {
+      name: self.name,
+      child: Arc::new(Mutex::new(child)),
+      stopped: Arc::new(AtomicBool::new(false)),
+      finished: finished_rx,
+    };
+    tokio::spawn(supervise(
+      command,
+      self.restart_policy,
+      events,
+      sidecar.clone(),
+      tx,
+      finished_tx,
+    ));
+    Ok((rx, sidecar))
+  }
+}
+
+/// Forwards the sidecar events and restarts it according to the policy.
+async fn supervise(
+  command: CommandBuilder,
+  restart_policy: RestartPolicy,
+  mut events: Receiver<CommandEvent>,
+  sidecar: Sidecar,
+  tx: Sender<SidecarEvent>,
+  finished_tx: watch::Sender<bool>,
+) {
+  let mut restarts = 0;
+  loop {
+    let mut status = None;
+    while let Some(event) = events.recv().await {
+      if let CommandEvent::Terminated(payload) = &event {
+        status = Some(payload.clone());
+      }
+      // the output is still read if the receiver was dropped
+      let _ = tx.send(event.into()).await;
+    }
+
+    let delay = match restart_policy.restart_delay(status.as_ref(), restarts) {
+      Some(delay) if !sidecar.is_stopped() => delay,
+      _ => break,
+    };
+    tokio::time::sleep(delay).await;
+    if sidecar.is_stopped() {
+      break;
+    }
+    restarts += 1;
+    match command.clone().spawn() {
+      Ok((new_events, child)) => {
+        let pid = child.pid();
+        events = new_events;
+        *sidecar.lock_child() = child;
+        // the sidecar could have been killed before the new child was stored
+        if sidecar.is_stopped() {
+          sidecar.lock_child().kill();
+        }
+        let _ = tx.send(SidecarEvent::Restarted(pid)).await;
+      }
+      Err(e) => {
+        let _ = tx.send(SidecarEvent::Error(e.to_string())).await;
+        break;
+      }
+    }
+  }
+  let _ = finished_tx.send(true);
+}
+
+/// A handle to a spawned sidecar. Its clones refer to the same sidecar.
+#[derive(Debug, Clone)]
+pub struct Sidecar {
+  name: String,
+  child: Arc<Mutex<CommandChild>>,
+  stopped: Arc<AtomicBool>,
+  finished: watch::Receiver<bool>,
+}
+
+impl Sidecar {
+  /// The sidecar name.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// The identifier of the current sidecar process.
+  pub fn pid(&self) -> u32 {
+    self.lock_child().pid()
+  }
+
+  /// Writes the data to the stdin of the current sidecar process.
+  pub async fn write(&self, data: &[u8]) -> crate::Result<()> {
+    let child = self.lock_child().clone();
+    child.write(data).await
+  }
+
+  /// Kills the sidecar without restarting it. Its termination is still sent as an event.
+  pub fn kill(&self) {
+    self.stopped.store(true, Ordering::SeqCst);
+    self.lock_child().kill();
+  }
+
+  /// Kills the sidecar and waits for it to terminate.
+  pub async fn stop(&self) {
+    self.kill();
+    let mut finished = self.finished.clone();
+    while !*finished.borrow() {
+      if finished.changed().await.is_err() {
+        break;
+      }
+    }
+  }
+
+  fn is_stopped(&self) -> bool {
+    self.stopped.load(Ordering::SeqCst)
+  }
+
+  fn lock_child(&self) -> std::sync::MutexGuard<'_, CommandChild> {
+    self
+      .child
+      .lock()
+      .expect("Failed to lock sidecar child: lock_child()")
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn check_restart_policy() {
+    let delay = Duration::from_millis(10);
+    let failure = TerminatedPayload {
+      code: Some(1),
+      signal: None,
+    };
+    let success = TerminatedPayload {
+      code: Some(0),
+      signal: None,
+    };
+    let on_failure = RestartPolicy::OnFailure {
+      max_restarts: 1,
+      delay,
+    };
+    assert_eq!(on_failure.restart_delay(Some(&failure), 0), Some(delay));
+    assert_eq!(on_failure.restart_delay(None, 0), Some(delay));
+    assert_eq!(on_failure.restart_delay(Some(&success), 0), None);
+    assert_eq!(on_failure.restart_delay(Some(&failure), 1), None);
+
+    let always = RestartPolicy::Always {
+      max_restarts: 2,
+      delay,
+    };
+    assert_eq!(always.restart_delay(Some(&success), 1), Some(delay));
+    assert_eq!(always.restart_delay(Some(&success), 2), None);
+    assert_eq!(RestartPolicy::Never.restart_delay(Some(&failure), 0), None);
+  }
+
+  #[cfg(not(windows))]
+  #[tokio::test]
+  // test restarting a failing sidecar until the policy gives up
+  async fn check_restart() {
+    let (mut events, _sidecar) = SidecarBuilder::new("crash")
+      .program("sh")
+      .args(vec!["-c".to_string(), "echo running; exit 1".to_string()])
+      .restart_policy(RestartPolicy::OnFailure {
+        max_restarts: 2,
+        delay: Duration::from_millis(10),
+      })
+      .spawn()
+      .unwrap();
+
+    let mut received = Vec::new();
+    while let Some(event) = events.recv().await {
+      received.push(event);
+    }
+    let count = |f: fn(&SidecarEvent) -> bool| received.iter().filter(|e| f(e)).count();
+    assert_eq!(count(|e| matches!(e, SidecarEvent::Stdout(_))), 3);
+    assert_eq!(count(|e| matches!(e, SidecarEvent::Restarted(_))), 2);
+    assert_eq!(count(|e| matches!(e, SidecarEvent::Terminated(_))), 3);
+  }
+
+  #[cfg(not(windows))]
+  #[tokio::test]
+  // test stopping a sidecar that would always be restarted
+  async fn check_stop() {
+    let (_events, sidecar) = SidecarBuilder::new("server")
+      .program("sleep")
+      .args(vec!["10".to_string()])
+      .restart_policy(RestartPolicy::Always {
+        max_restarts: 5,
+        delay: Duration::from_millis(10),
+      })
+      .spawn()
+      .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), sidecar.stop())
+      .await
+      .unwrap();
+  }
+}
@@ -0,0 +1,149 @@
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher as _};
+use serde::Serialize;
+use tokio::sync::mpsc::{self, Receiver};
+
+use std::{
+  path::{Path, PathBuf},
+  time::Duration,
+};
+
+/// The number of watch events buffered before the watcher waits for the receiver.
+const EVENT_BUFFER: usize = 32;
+
+/// A file system change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "payload", rename_all = "camelCase")]
+pub enum WatchEvent {
+  /// A file or directory was created.
+  Create(PathBuf),
+  /// A file was written to, or its attributes changed.
+  Modify(PathBuf),
+  /// A file or directory was removed.
+  Remove(PathBuf),
+  /// A file or directory was moved within the watched path.
+  Rename {
+    /// The previous path.
+    from: PathBuf,
+    /// The new path.
+    to: PathBuf,
+  },
+  /// Watching the path failed.
+  Error(String),
+}
+
+impl WatchEvent {
+  /// Maps the notify event, ignoring the notices sent before the debounced events.
+  fn from_debounced(event: DebouncedEvent) -> Option<Self> {
+    match event {
+      DebouncedEvent::Create(path) => Some(Self::Create(path)),
+      DebouncedEvent::Write(path) | DebouncedEvent::Chmod(path) => Some(Self::Modify(path)),
+      DebouncedEvent::Remove(path) => Some(Self::Remove(path)),
+      DebouncedEvent::Rename(from, to) => Some(Self::Rename { from, to }),
+      DebouncedEvent::Error(error, _) => Some(Self::Error(error.to_string())),
+      DebouncedEvent::NoticeWrite(_) | DebouncedEvent::NoticeRemove(_) | DebouncedEvent::Rescan => {
+        None
+      }
+    }
+  }
+}
+
+/// Watches a path for changes, returning the changes and a handle that stops watching when dropped.
+///
+/// The events of a path are debounced: only the last one is sent once no change happened for the given duration.
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use tauri_api::watcher::watch;
+///
+/// # async fn run() -> tauri_api::Result<()> {
+/// let (mut events, _watcher) = watch("src", true, Duration::from_millis(100))?;
+/// while let Some(event) = events.recv().await {
+///   println!("changed: {:?}", event);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn watch(
+  path: impl AsRef<Path>,
+  recursive: bool,
+  debounce: Duration,
+) -> crate::Result<(Receiver<WatchEvent>, Watcher)> {
+  let (notify_tx, notify_rx) = std::sync::mpsc::channel();
+  let mut watcher = notify::watcher(notify_tx, debounce)?;
+  let mode = if recursive {
+    RecursiveMode::Recursive
+  } else {
+    RecursiveMode::NonRecursive
+  };
+  watcher.watch(path, mode)?;
+
+  let (tx, rx) = mpsc::channel(EVENT_BUFFER);
+  // the notify channel is closed once the watcher is dropped
+  std::thread::spawn(move || {
+    for event in notify_rx {
+      if let Some(event) = WatchEvent::from_debounced(event) {
+        if tx.blocking_send(event).is_err() {
+          break;
+        }
+      }
+    }
+  });
+
+  Ok((rx, Watcher { _watcher: watcher }))
+}
+
+/// A file system watcher. The path is no longer watched once it's dropped.
+pub struct Watcher {
+  _watcher: RecommendedWatcher,
+}
+
+impl std::fmt::Debug for Watcher {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Watcher").finish()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{watch, WatchEvent};
+
+  use std::time::Duration;
+  use tokio::sync::mpsc::Receiver;
+
+  async fn next(events: &mut Receiver<WatchEvent>) -> WatchEvent {
+    tokio::time::timeout(Duration::from_secs(5), events.recv())
+      .await
+      .unwrap()
+      .unwrap()
+  }
+
+  #[tokio::test]
+  // test the create, modify, rename and remove events of a watched directory
+  async fn check_watch() {
+    let dir = tempfile::tempdir().unwrap();
+    let dir = dir.path().canonicalize().unwrap();
+    let (mut events, _watcher) = watch(&dir, false, Duration::from_millis(50)).unwrap();
+    let file = dir.join("file.txt");
+
+    std::fs::write(&file, "tauri").unwrap();
+    assert_eq!(next(&mut events).await, WatchEvent::Create(file.clone()));
+
+    std::thread::sleep(Duration::from_millis(100));
+    std::fs::write(&file, "tauri!").unwrap();
+    assert_eq!(next(&mut events).await, WatchEvent::Modify(file.clone()));
+
+    let renamed = dir.join("renamed.txt");
+    std::fs::rename(&file, &renamed).unwrap();
+    assert_eq!(
+      next(&mut events).await,
+      WatchEvent::Rename {
+        from: file,
+        to: renamed.clone()
+      }
+    );
+
+    std::fs::remove_file(&renamed).unwrap();
+    assert_eq!(next(&mut events).await, WatchEvent::Remove(renamed));
+  }
+}
@@ -0,0 +1,303 @@
+use crate::http::Scope;
+
+use futures_util::{
+  stream::{SplitSink, SplitStream},
+  SinkExt, StreamExt,
+};
+use reqwest::{
+  header::{HeaderName, HeaderValue},
+  Url,
+};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+  tungstenite::{
+    self,
+    client::IntoClientRequest,
+    protocol::{frame::coding::CloseCode, CloseFrame as TungsteniteCloseFrame},
+  },
+  MaybeTlsStream, WebSocketStream,
+};
+
+use std::collections::HashMap;
+
+type Stream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// The WebSocket connection request.
+///
+/// # Examples
+/// ```no_run
+/// use tauri_api::websocket::{connect, ConnectRequestBuilder, Event, Message};
+///
+/// # async fn run() -> tauri_api::Result<()> {
+/// let (mut sender, mut receiver) = connect(ConnectRequestBuilder::new("wss://echo.tauri.studio")).await?;
+/// sender.send(Message::Text("ping".into())).await?;
+/// while let Some(event) = receiver.next().await {
+///   match event? {
+///     Event::Message(message) => println!("received {:?}", message),
+///     Event::Close(_) => break,
+///   }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectRequestBuilder {
+  /// The WebSocket URL, using the `ws` or `wss` scheme.
+  pub url: String,
+  /// The handshake request headers.
+  pub headers: Option<HashMap<String, String>>,
+  /// The URLs allowed to be connected to. Every URL is allowed if not set.
+  ///
+  /// The scope patterns must use the `ws` or `wss` scheme to allow WebSocket URLs.
+  #[serde(skip)]
+  pub scope: Option<Scope>,
+}
+
+impl ConnectRequestBuilder {
+  /// Initializes a new connection request.
+  pub fn new(url: impl Into<String>) -> Self {
+    Self {
+      url: url.into(),
+      headers: None,
+      scope: None,
+    }
+  }
+
+  /// Sets the handshake request headers.
+  pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+    self.headers = Some(headers);
+    self
+  }
+
+  /// Sets the URLs allowed to be connected to.
+  pub fn scope(mut self, scope: Scope) -> Self {
+    self.scope = Some(scope);
+    self
+  }
+}
+
+/// A WebSocket data message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum Message {
+  /// A text message.
+  Text(String),
+  /// A binary message.
+  Binary(Vec<u8>),
+}
+
+impl From<Message> for tungstenite::Message {
+  fn from(message: Message) -> Self {
+    match message {
+      Message::Text(text) => Self::Text(text),
+      Message::Binary(data) => Self::Binary(data),
+    }
+  }
+}
+
+/// The close frame of a WebSocket connection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CloseFrame {
+  /// The close status code, e.g. 1000 for a normal closure.
+  pub code: u16,
+  /// The close reason.
+  #[serde(default)]
+  pub reason: String,
+}
+
+impl From<CloseFrame> for TungsteniteCloseFrame<'static> {
+  fn from(frame: CloseFrame) -> Self {
+    Self {
+      code: CloseCode::from(frame.code),
+      reason: frame.reason.into(),
+    }
+  }
+}
+
+impl From<TungsteniteCloseFrame<'_>> for CloseFrame {
+  fn from(frame: TungsteniteCloseFrame<'_>) -> Self {
+    Self {
+      code: frame.code.into(),
+      reason: frame.reason.into_owned(),
+    }
+  }
+}
+
+/// An event received on a WebSocket connection.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum Event {
+  /// A data message was received.
+  Message(Message),
+  /// The connection was closed by the server, or the close handshake finished.
+  Close(Option<CloseFrame>),
+}
+
+/// Connects to a WebSocket server, returning the sending and the receiving halves of the connection.
+///
+/// Pings are answered automatically while the receiving half is polled.
+pub async fn connect(
+  request: ConnectRequestBuilder,
+) -> crate::Result<(WebSocketSender, WebSocketReceiver)> {
+  let url = Url::parse(&request.url)
+    .map_err(|e| crate::Error::InvalidUrl(format!("{}: {}", request.url, e)))?;
+  if let Some(scope) = &request.scope {
+    if !scope.is_allowed(&url) {
+      return Err(crate::Error::UrlNotAllowed(url.to_string()));
+    }
+  }
+
+  let mut handshake = url.as_str().into_client_request()?;
+  if let Some(headers) = request.headers {
+    for (name, value) in headers {
+      handshake.headers_mut().insert(
+        HeaderName::from_bytes(name.as_bytes())?,
+        HeaderValue::from_str(&value)?,
+      );
+    }
+  }
+
+  let (stream, _) = tokio_tungstenite::connect_async(handshake).await?;
+  let (sink, stream) = stream.split();
+  Ok((WebSocketSender(sink), WebSocketReceiver(stream)))
+}
+
+/// The sending half of a WebSocket connection.
+pub struct WebSocketSender(SplitSink<Stream, tungstenite::Message>);
+
+impl WebSocketSender {
+  /// Sends a data message.
+  pub async fn send(&mut self, message: Message) -> crate::Result<()> {
+    self.0.send(message.into()).await.map_err(Into::into)
+  }
+
+  /// Starts the close handshake. The receiving half yields [`Event::Close`] once the server replies.
+  pub async fn close(&mut self, frame: Option<CloseFrame>) -> crate::Result<()> {
+    self
+      .0
+      .send(tungstenite::Message::Close(frame.map(Into::into)))
+      .await
+      .map_err(Into::into)
+  }
+}
+
+/// The receiving half of a WebSocket connection.
+pub struct WebSocketReceiver(SplitStream<Stream>);
+
+impl WebSocketReceiver {
+  /// Waits for the next event, returning `None` once the connection is closed.
+  pub async fn next(&mut self) -> Option<crate::Result<Event>> {
+    loop {
+      let message = match self.0.next().await? {
+        Ok(message) => message,
+        // the connection was closed after the close event
+        Err(tungstenite::Error::ConnectionClosed) => return None,
+        Err(e) => return Some(Err(e.into())),
+      };
+      let event = match message {
+        tungstenite::Message::Text(text) => Event::Message(Message::Text(text)),
+        tungstenite::Message::Binary(data) => Event::Message(Message::Binary(data)),
+        tungstenite::Message::Close(frame) => Event::Close(frame.map(Into::into)),
+        // pings are answered by tungstenite
+        tungstenite::Message::Ping(_) | tungstenite::Message::Pong(_) => continue,
+      };
+      return Some(Ok(event));
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{connect, CloseFrame, ConnectRequestBuilder, Event, Message};
+  use crate::http::Scope;
+  use futures_util::{SinkExt, StreamExt};
+  use tokio_tungstenite::tungstenite;
+
+  use std::collections::HashMap;
+
+  /// Records the `Authorization` header of the handshake request.
+  struct RecordAuthorization<'a>(&'a mut Option<String>);
+
+  impl tungstenite::handshake::server::Callback for RecordAuthorization<'_> {
+    fn on_request(
+      self,
+      request: &tungstenite::handshake::server::Request,
+      response: tungstenite::handshake::server::Response,
+    ) -> Result<
+      tungstenite::handshake::server::Response,
+      tungstenite::handshake::server::ErrorResponse,
+    > {
+      *self.0 = request
+        .headers()
+        .get("authorization")
+        .map(|value| value.to_str().unwrap().to_string());
+      Ok(response)
+    }
+  }
+
+  /// Accepts one connection, sends the `Authorization` header back and echoes the messages.
+  async fn serve() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}/socket", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+      let (stream, _) = listener.accept().await.unwrap();
+      let mut authorization = None;
+      let mut stream =
+        tokio_tungstenite::accept_hdr_async(stream, RecordAuthorization(&mut authorization))
+          .await
+          .unwrap();
+      let authorization = authorization.unwrap_or_default();
+      stream.send(authorization.into()).await.unwrap();
+      // the close reply is sent when polling the stream after the close frame
+      while let Some(Ok(message)) = stream.next().await {
+        if message.is_text() || message.is_binary() {
+          stream.send(message).await.unwrap();
+        }
+      }
+    });
+    url
+  }
+
+  #[tokio::test]
+  async fn check_connection() {
+    let url = serve().await;
+    let mut headers = HashMap::new();
+    headers.insert("Authorization".to_string(), "Bearer tauri".to_string());
+    let request = ConnectRequestBuilder::new(url).headers(headers);
+    let (mut sender, mut receiver) = connect(request).await.unwrap();
+
+    let text = Event::Message(Message::Text("Bearer tauri".into()));
+    assert_eq!(receiver.next().await.unwrap().unwrap(), text);
+
+    let binary = Message::Binary(vec![1, 2, 3]);
+    sender.send(binary.clone()).await.unwrap();
+    assert_eq!(
+      receiver.next().await.unwrap().unwrap(),
+      Event::Message(binary)
+    );
+
+    let frame = CloseFrame {
+      code: 1000,
+      reason: "done".into(),
+    };
+    sender.close(Some(frame)).await.unwrap();
+    // the server replies with the close code
+    assert!(matches!(
+      receiver.next().await.unwrap().unwrap(),
+      Event::Close(Some(CloseFrame { code: 1000, .. }))
+    ));
+    assert!(receiver.next().await.is_none());
+  }
+
+  #[tokio::test]
+  async fn check_scope() {
+    let scope = Scope::new(&["wss://*.tauri.studio"]).unwrap();
+    let request = ConnectRequestBuilder::new("ws://localhost:1").scope(scope);
+    assert!(matches!(
+      connect(request).await,
+      Err(crate::Error::UrlNotAllowed(_))
+    ));
+  }
+}
@@ -306,6 +306,149 @@ impl Default for BundleConfig {
   }
 }
 
+/// The async runtime configuration object.
+/// Only used when Tauri creates its own runtime.
+#[derive(PartialEq, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AsyncRuntimeConfig {
+  /// The number of worker threads. Defaults to the number of cores available to the system.
+  pub worker_threads: Option<usize>,
+  /// The limit for additional threads spawned by the runtime to run blocking tasks.
+  pub max_blocking_threads: Option<usize>,
+}
+
+fn default_max_clients_per_window() -> usize {
+  10
+}
+
+/// The window API allowlist configuration object.
+#[derive(PartialEq, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowAllowlistConfig {
+  /// The URL patterns the window navigate API can load, e.g. `https://*.tauri.studio/`.
+  /// A pattern is made of a scheme, a host glob where `*` matches any sequence of characters and a path prefix.
+  ///
+  /// Navigating to any other URL is rejected.
+  #[serde(default)]
+  pub navigation_scope: Vec<String>,
+}
+
+/// The HTTP API allowlist configuration object.
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpAllowlistConfig {
+  /// The URL patterns the HTTP and WebSocket APIs can access, e.g. `https://*.tauri.studio/api/` or `wss://*.tauri.studio`.
+  /// A pattern is made of a scheme, a host glob where `*` matches any sequence of characters and a path prefix.
+  ///
+  /// If not set, every URL is allowed.
+  pub scope: Option<Vec<String>>,
+  /// The maximum number of live HTTP clients a window can create.
+  #[serde(default = "default_max_clients_per_window")]
+  pub max_clients_per_window: usize,
+}
+
+impl Default for HttpAllowlistConfig {
+  fn default() -> Self {
+    Self {
+      scope: None,
+      max_clients_per_window: default_max_clients_per_window(),
+    }
+  }
+}
+
+/// An argument of a shell scope command.
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ShellAllowedArg {
+  /// An argument that must be equal to the string.
+  Fixed(String),
+  /// An argument that must match the regular expression, e.g. `{ "validator": "^\\w+$" }`.
+  Var {
+    /// The regular expression. It must match the whole argument.
+    validator: String,
+  },
+}
+
+/// The arguments of a shell scope command.
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ShellAllowedArgs {
+  /// `true` allows any arguments and `false` allows none.
+  Flag(bool),
+  /// The allowed arguments, one per position.
+  List(Vec<ShellAllowedArg>),
+}
+
+impl Default for ShellAllowedArgs {
+  fn default() -> Self {
+    Self::Flag(false)
+  }
+}
+
+/// A command the shell execute API is allowed to spawn.
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellAllowedCommand {
+  /// The name used by the shell execute API to refer to the command.
+  pub name: String,
+  /// The program path, or the external binary name if the command is a sidecar.
+  pub cmd: String,
+  /// Whether the command is an external binary bundled with the app.
+  #[serde(default)]
+  pub sidecar: bool,
+  /// The allowed arguments. Defaults to no arguments.
+  #[serde(default)]
+  pub args: ShellAllowedArgs,
+  /// Whether the shell execute API can set the working directory of the command.
+  #[serde(default)]
+  pub cwd: bool,
+  /// The environment variables the shell execute API can set on the command.
+  #[serde(default)]
+  pub env: Vec<String>,
+}
+
+fn default_open_schemes() -> Vec<String> {
+  vec!["https".to_string(), "mailto".to_string()]
+}
+
+/// The shell API allowlist configuration object.
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellAllowlistConfig {
+  /// The commands the shell execute API can spawn, referred to by their name.
+  #[serde(default)]
+  pub scope: Vec<ShellAllowedCommand>,
+  /// The URL schemes the shell open API can open. Paths are allowed with the `file` scheme.
+  #[serde(default = "default_open_schemes")]
+  pub open_schemes: Vec<String>,
+}
+
+impl Default for ShellAllowlistConfig {
+  fn default() -> Self {
+    Self {
+      scope: Vec::new(),
+      open_schemes: default_open_schemes(),
+    }
+  }
+}
+
+/// The allowlist configuration object.
+///
+/// The APIs are enabled with Cargo features, this object only holds their runtime configuration.
+#[derive(PartialEq, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AllowlistConfig {
+  /// The window API allowlist configuration.
+  #[serde(default)]
+  pub window: WindowAllowlistConfig,
+  /// The HTTP API allowlist configuration.
+  #[serde(default)]
+  pub http: HttpAllowlistConfig,
+  /// The shell API allowlist configuration.
+  #[serde(default)]
+  pub shell: ShellAllowlistConfig,
+}
+
 fn default_window_config() -> Vec<WindowConfig> {
   vec![Default::default()]
 }
@@ -323,6 +466,12 @@ pub struct TauriConfig {
   /// The bundler configuration.
   #[serde(default)]
   pub bundle: BundleConfig,
+  /// The async runtime configuration.
+  #[serde(default)]
+  pub async_runtime: AsyncRuntimeConfig,
+  /// The allowlist configuration.
+  #[serde(default)]
+  pub allowlist: AllowlistConfig,
 }
 
 impl Default for TauriConfig {
@@ -331,6 +480,8 @@ impl Default for TauriConfig {
       windows: default_window_config(),
       cli: None,
       bundle: BundleConfig::default(),
+      async_runtime: AsyncRuntimeConfig::default(),
+      allowlist: AllowlistConfig::default(),
     }
   }
 }
@@ -442,6 +593,23 @@ mod test {
         identifier: String::from(""),
       },
       cli: None,
+      async_runtime: AsyncRuntimeConfig {
+        worker_threads: None,
+        max_blocking_threads: None,
+      },
+      allowlist: AllowlistConfig {
+        window: WindowAllowlistConfig {
+          navigation_scope: vec![],
+        },
+        http: HttpAllowlistConfig {
+          scope: None,
+          max_clients_per_window: 10,
+        },
+        shell: ShellAllowlistConfig {
+          scope: vec![],
+          open_schemes: vec!["https".to_string(), "mailto".to_string()],
+        },
+      },
     };
 
     // create a build config
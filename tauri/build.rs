@@ -20,10 +20,13 @@ fn main() {
     fs_remove_file: { any(fs_all, feature = "fs-remove-file") },
     fs_rename_file: { any(fs_all, feature = "fs-rename-file") },
     fs_path: { any(fs_all, feature = "fs-path") },
+    fs_watch: { any(fs_all, feature = "fs-watch") },
+    fs_open: { any(fs_all, feature = "fs-open") },
 
     // window
     window_all: { any(api_all, feature = "window-all") },
     window_create: { any(window_all, feature = "window-create") },
+    window_navigate: { any(window_all, feature = "window-navigate") },
 
     // shell
     shell_all: { any(api_all, feature = "shell-all") },
@@ -38,6 +41,8 @@ fn main() {
     // http
     http_all: { any(api_all, feature = "http-all") },
     http_request: { any(http_all, feature = "http-request") },
+    http_download: { any(http_all, feature = "http-download") },
+    http_websocket: { any(http_all, feature = "http-websocket") },
 
     // cli
     cli: { feature = "cli" },
@@ -47,5 +52,13 @@ fn main() {
 
     // global shortcut
     global_shortcut_all: { any(api_all, feature = "global_shortcut-all") },
+
+    // store
+    store_all: { any(api_all, feature = "store-all") },
+
+    // clipboard
+    clipboard_all: { any(api_all, feature = "clipboard-all") },
+    clipboard_read: { any(clipboard_all, feature = "clipboard-read") },
+    clipboard_write: { any(clipboard_all, feature = "clipboard-write") },
   }
 }
@@ -3,11 +3,17 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use tauri_api::{config::Config, private::AsTauriContext};
 
-use crate::async_runtime::Mutex;
+use crate::{
+  async_runtime::Mutex,
+  crash::{CrashReport, CrashReporter},
+};
 
 use std::{collections::HashMap, marker::PhantomData, sync::Arc};
 
+pub(crate) mod eval;
 pub(crate) mod event;
+mod main_thread;
+mod sidecar;
 mod utils;
 pub(crate) mod webview;
 mod webview_manager;
@@ -24,6 +30,7 @@ type InvokeHandler<A> = dyn Fn(WebviewManager<A>, String) -> BoxFuture<'static,
   + Send
   + Sync;
 type Setup<A> = dyn Fn(WebviewManager<A>) -> BoxFuture<'static, ()> + Send + Sync;
+type OnPreviousCrash = dyn FnMut(crate::Result<CrashReport>) + Send;
 
 /// `App` runtime information.
 pub struct Context {
@@ -186,12 +193,14 @@ impl<A: ApplicationExt + 'static> WebviewInitializer<A> for Arc<App<A>> {
     dispatcher: A::Dispatcher,
     manager: WebviewManager<A>,
   ) {
-    self.dispatchers.lock().await.insert(
-      webview_label.to_string(),
-      WebviewDispatcher::new(dispatcher.clone(), webview_label),
-    );
+    let webview_dispatcher = WebviewDispatcher::new(dispatcher.clone(), webview_label.to_string());
+    self
+      .dispatchers
+      .lock()
+      .await
+      .insert(webview_label, webview_dispatcher);
 
-    crate::async_runtime::spawn_task(async move {
+    crate::async_runtime::spawn(async move {
       crate::plugin::created(A::plugin_store(), &manager).await
     });
   }
@@ -207,6 +216,12 @@ where
   invoke_handler: Option<Box<InvokeHandler<A>>>,
   /// The setup callback, invoked when the webview is ready.
   setup: Option<Box<Setup<A>>>,
+  /// The crash reporter installed when the app is built.
+  crash_reporter: Option<CrashReporter>,
+  /// The callback invoked with the crash reports saved by previous runs.
+  on_previous_crash: Option<Box<OnPreviousCrash>>,
+  /// The handle to the app's async runtime.
+  async_runtime: Option<tokio::runtime::Handle>,
   config: PhantomData<C>,
   /// The webview dispatchers.
   dispatchers: Arc<Mutex<HashMap<String, WebviewDispatcher<A::Dispatcher>>>>,
@@ -220,6 +235,9 @@ impl<A: ApplicationExt + 'static, C: AsTauriContext> AppBuilder<C, A> {
     Self {
       invoke_handler: None,
       setup: None,
+      crash_reporter: None,
+      on_previous_crash: None,
+      async_runtime: None,
       config: Default::default(),
       dispatchers: Default::default(),
       webviews: Default::default(),
@@ -254,12 +272,39 @@ impl<A: ApplicationExt + 'static, C: AsTauriContext> AppBuilder<C, A> {
     self
   }
 
+  /// Installs a panic hook that saves a crash report to the app directory.
+  pub fn crash_reporter(mut self, crash_reporter: CrashReporter) -> Self {
+    self.crash_reporter = Some(crash_reporter);
+    self
+  }
+
+  /// Defines the callback invoked with each crash report saved by the previous runs of the app,
+  /// e.g. to ask the user to send it. The reports that can't be read are passed as errors.
+  /// The callback is invoked when the app is built, before the crash reporter is installed.
+  pub fn on_previous_crash<F: FnMut(crate::Result<CrashReport>) + Send + 'static>(
+    mut self,
+    on_previous_crash: F,
+  ) -> Self {
+    self.on_previous_crash = Some(Box::new(on_previous_crash));
+    self
+  }
+
+  /// Runs the Tauri async tasks on the given runtime instead of creating a new one.
+  ///
+  /// The `tauri > asyncRuntime` config is ignored when an existing runtime is used.
+  /// Building the app fails if the Tauri async runtime was already used, e.g. by [`crate::async_runtime::spawn`] or a previous app build.
+  pub fn async_runtime(mut self, handle: tokio::runtime::Handle) -> Self {
+    self.async_runtime = Some(handle);
+    self
+  }
+
   /// Adds a plugin to the runtime.
   pub fn plugin(
     self,
     plugin: impl crate::plugin::Plugin<A> + Send + Sync + Sync + 'static,
   ) -> Self {
-    crate::async_runtime::block_on(crate::plugin::register(A::plugin_store(), plugin));
+    // the async runtime is only initialized on `build`
+    futures::executor::block_on(crate::plugin::register(A::plugin_store(), plugin));
     self
   }
 
@@ -281,11 +326,23 @@ impl<A: ApplicationExt + 'static, C: AsTauriContext> AppBuilder<C, A> {
 
   /// Builds the App.
   pub fn build(self) -> crate::Result<App<A>> {
+    let context = Context::new::<C>()?;
+    crate::async_runtime::init(self.async_runtime, &context.config.tauri.async_runtime)?;
+
+    if let Some(mut on_previous_crash) = self.on_previous_crash {
+      let crash_reporter = self.crash_reporter.clone().unwrap_or_default();
+      for report in crash_reporter.take_pending_reports()? {
+        on_previous_crash(report);
+      }
+    }
+    if let Some(crash_reporter) = self.crash_reporter {
+      crash_reporter.install()?;
+    }
+
     let window_labels: Vec<String> = self.webviews.iter().map(|w| w.label.to_string()).collect();
     let plugin_initialization_script =
       crate::async_runtime::block_on(crate::plugin::initialization_script(A::plugin_store()));
 
-    let context = Context::new::<C>()?;
     let url = utils::get_url(&context);
 
     Ok(App {
@@ -312,6 +369,13 @@ fn run<A: ApplicationExt + 'static>(mut application: App<A>) -> crate::Result<()
   let application = Arc::new(application);
   let mut webview_app = A::new()?;
 
+  // the store changes are emitted to all windows, so the manager isn't tied to a window
+  crate::store::emit_changes(WebviewManager::new(
+    application.clone(),
+    application.dispatchers.clone(),
+    String::new(),
+  ));
+
   for webview in webviews {
     let webview_label = webview.label.to_string();
     let webview_manager = WebviewManager::new(
@@ -0,0 +1,137 @@
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex},
+};
+
+use once_cell::sync::Lazy;
+use serde_json::Value as JsonValue;
+use tokio::sync::oneshot::{channel, Receiver, Sender};
+
+/// The result of a script evaluation: the JSON value or the thrown JS error.
+pub(crate) type EvalResult = Result<JsonValue, String>;
+
+/// A script evaluation waiting for its result.
+struct PendingEval {
+  /// The label of the window evaluating the script.
+  window_label: String,
+  /// The sender of the evaluation result.
+  tx: Sender<EvalResult>,
+}
+
+type PendingEvals = Arc<Mutex<HashMap<String, PendingEval>>>;
+
+/// Gets the scripts waiting for their evaluation result.
+fn pending_evals() -> &'static PendingEvals {
+  static PENDING_EVALS: Lazy<PendingEvals> = Lazy::new(Default::default);
+  &PENDING_EVALS
+}
+
+/// Registers a script evaluation on the given window.
+/// Returns its correlation ID and the receiver of its result.
+pub(crate) fn register(window_label: &str) -> (String, Receiver<EvalResult>) {
+  crate::event::on_window_destroyed("eval", unregister_window);
+  let id = uuid::Uuid::new_v4().to_string();
+  let (tx, rx) = channel();
+  pending_evals()
+    .lock()
+    .expect("Failed to lock pending evals: register()")
+    .insert(
+      id.clone(),
+      PendingEval {
+        window_label: window_label.to_string(),
+        tx,
+      },
+    );
+  (id, rx)
+}
+
+/// Removes a script evaluation that won't be resolved.
+pub(crate) fn unregister(id: &str) {
+  pending_evals()
+    .lock()
+    .expect("Failed to lock pending evals: unregister()")
+    .remove(id);
+}
+
+/// Removes the script evaluations of a destroyed window.
+fn unregister_window(window_label: &str) {
+  pending_evals()
+    .lock()
+    .expect("Failed to lock pending evals: unregister_window()")
+    .retain(|_, pending| pending.window_label != window_label);
+}
+
+/// Resolves the script evaluation with the given correlation ID.
+/// Results sent by a window other than the one evaluating the script are ignored.
+pub(crate) fn resolve(id: &str, window_label: &str, result: EvalResult) {
+  let mut pending_evals = pending_evals()
+    .lock()
+    .expect("Failed to lock pending evals: resolve()");
+  if pending_evals
+    .get(id)
+    .map_or(false, |pending| pending.window_label == window_label)
+  {
+    if let Some(pending) = pending_evals.remove(id) {
+      let _ = pending.tx.send(result);
+    }
+  }
+}
+
+/// Wraps the script so its result (or the thrown error) is sent back through the invoke channel.
+/// Promises are awaited before sending the result.
+pub(crate) fn script(id: &str, js: &str) -> String {
+  format!(
+    r#"
+      (function () {{
+        function send(message) {{
+          message.cmd = 'evalResult'
+          message.id = '{id}'
+          window.__TAURI__.invoke({{
+            __tauriModule: 'Internal',
+            message: message
+          }})
+        }}
+        try {{
+          Promise.resolve(eval({js})).then(function (result) {{
+            send({{ result: result === undefined ? null : result }})
+          }}, function (error) {{
+            send({{ error: String(error) }})
+          }})
+        }} catch (error) {{
+          send({{ error: String(error) }})
+        }}
+      }})()
+    "#,
+    id = id,
+    js = JsonValue::String(js.to_string())
+  )
+}
+
+#[cfg(test)]
+mod test {
+  use serde_json::json;
+
+  #[test]
+  fn check_resolve() {
+    let (id, rx) = super::register("main");
+    let (error_id, error_rx) = super::register("main");
+    // results from other windows are ignored
+    super::resolve(&id, "other", Ok(json!(null)));
+    super::resolve(&id, "main", Ok(json!({ "title": "Tauri" })));
+    super::resolve(
+      &error_id,
+      "main",
+      Err("ReferenceError: x is not defined".to_string()),
+    );
+
+    assert_eq!(
+      crate::async_runtime::block_on(rx).unwrap(),
+      Ok(json!({ "title": "Tauri" }))
+    );
+    assert_eq!(
+      crate::async_runtime::block_on(error_rx).unwrap(),
+      Err("ReferenceError: x is not defined".to_string())
+    );
+    assert!(!super::pending_evals().lock().unwrap().contains_key(&id));
+  }
+}
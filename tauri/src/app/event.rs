@@ -26,6 +26,44 @@ lazy_static! {
   static ref EVENT_QUEUE_OBJECT_NAME: String = uuid::Uuid::new_v4().to_string();
 }
 
+/// The event triggered on the Rust listeners of a window when its page is unloaded,
+/// either because the window is closed or because it navigates away.
+pub(crate) const WINDOW_DESTROYED_EVENT: &str = "tauri://destroyed";
+
+/// A function releasing the resources owned by a destroyed window, called with the window label.
+type WindowDestroyedHook = fn(&str);
+
+/// Gets the window destroyed hooks, identified by the name of the resources they release.
+fn window_destroyed_hooks() -> &'static Mutex<HashMap<&'static str, WindowDestroyedHook>> {
+  static HOOKS: Lazy<Mutex<HashMap<&'static str, WindowDestroyedHook>>> =
+    Lazy::new(Default::default);
+  &HOOKS
+}
+
+/// Registers a hook releasing the resources owned by a window when it's destroyed.
+/// The hook is registered once per name and runs for every destroyed window.
+pub(crate) fn on_window_destroyed(name: &'static str, hook: WindowDestroyedHook) {
+  window_destroyed_hooks()
+    .lock()
+    .expect("Failed to lock window destroyed hooks: on_window_destroyed()")
+    .entry(name)
+    .or_insert(hook);
+}
+
+/// Triggers the window destroyed event and releases the resources owned by the window.
+pub(crate) fn window_destroyed(window_label: &str) {
+  on_event(WINDOW_DESTROYED_EVENT.to_string(), Some(window_label), None);
+  let hooks: Vec<WindowDestroyedHook> = window_destroyed_hooks()
+    .lock()
+    .expect("Failed to lock window destroyed hooks: window_destroyed()")
+    .values()
+    .copied()
+    .collect();
+  for hook in hooks {
+    hook(window_label);
+  }
+}
+
 /// Gets the listeners map.
 fn listeners() -> &'static Listeners {
   static LISTENERS: Lazy<Listeners> = Lazy::new(Default::default);
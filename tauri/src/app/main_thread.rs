@@ -0,0 +1,129 @@
+use futures::{
+  future::BoxFuture,
+  task::{waker_ref, ArcWake},
+  Future,
+};
+
+use std::{
+  sync::{Arc, Mutex},
+  task::Context,
+};
+
+use super::ApplicationDispatcherExt;
+use crate::SyncTask;
+
+/// Queues a task on the main thread.
+type Scheduler = Arc<dyn Fn(SyncTask) -> crate::Result<()> + Send + Sync>;
+
+/// A future polled on the main thread.
+struct MainThreadTask {
+  /// The future, `None` once it's completed.
+  future: Mutex<Option<BoxFuture<'static, ()>>>,
+  scheduler: Scheduler,
+}
+
+impl MainThreadTask {
+  /// Queues a poll of the future on the main thread.
+  fn schedule(self: &Arc<Self>) {
+    let task = self.clone();
+    if let Err(e) = (self.scheduler)(Box::new(move || task.poll())) {
+      log::error!("failed to schedule the main thread task: {}", e);
+    }
+  }
+
+  /// Polls the future. The future runs in the context of the async runtime so it can use its resources.
+  fn poll(self: Arc<Self>) {
+    let mut future = self
+      .future
+      .lock()
+      .expect("Failed to lock main thread task: poll()");
+    if let Some(mut fut) = future.take() {
+      let handle = crate::async_runtime::handle();
+      let _guard = handle.enter();
+      let waker = waker_ref(&self);
+      if fut
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending()
+      {
+        *future = Some(fut);
+      }
+    }
+  }
+}
+
+impl ArcWake for MainThreadTask {
+  fn wake_by_ref(arc_self: &Arc<Self>) {
+    arc_self.schedule();
+  }
+}
+
+/// Runs the future on the main thread with the given scheduler.
+fn spawn_with(scheduler: Scheduler, future: impl Future<Output = ()> + Send + 'static) {
+  let task = Arc::new(MainThreadTask {
+    future: Mutex::new(Some(Box::pin(future))),
+    scheduler,
+  });
+  task.schedule();
+}
+
+/// Runs the future on the main thread.
+///
+/// The first poll and every poll after a wake up are queued on the main thread through the dispatcher,
+/// so the future never blocks the event loop while waiting.
+pub(crate) fn spawn<D: ApplicationDispatcherExt + 'static>(
+  dispatcher: D,
+  future: impl Future<Output = ()> + Send + 'static,
+) {
+  spawn_with(
+    Arc::new(move |task| dispatcher.run_on_main_thread(task)),
+    future,
+  )
+}
+
+#[cfg(test)]
+mod test {
+  use super::spawn_with;
+  use crate::SyncTask;
+
+  use std::{
+    sync::{mpsc::channel, Arc, Mutex},
+    thread::ThreadId,
+  };
+  use tokio::sync::oneshot;
+
+  #[test]
+  fn check_spawn() {
+    let main_thread = std::thread::current().id();
+    let (task_tx, task_rx) = channel::<SyncTask>();
+    let task_tx = Mutex::new(task_tx);
+    let (tx, rx) = oneshot::channel::<()>();
+    let (done_tx, done_rx) = channel::<(ThreadId, ThreadId)>();
+    spawn_with(
+      Arc::new(move |task| {
+        task_tx.lock().unwrap().send(task).unwrap();
+        Ok(())
+      }),
+      async move {
+        let first_thread = std::thread::current().id();
+        rx.await.unwrap();
+        done_tx
+          .send((first_thread, std::thread::current().id()))
+          .unwrap();
+      },
+    );
+    // nothing runs until the main thread drains the queue
+    assert!(done_rx.try_recv().is_err());
+    task_rx.recv().unwrap()();
+    assert!(done_rx.try_recv().is_err());
+
+    // the wake up is sent from another thread and the poll is queued on the main thread
+    std::thread::spawn(move || tx.send(()).unwrap())
+      .join()
+      .unwrap();
+    task_rx.recv().unwrap()();
+    let (first_thread, last_thread) = done_rx.recv().unwrap();
+    assert_eq!(first_thread, main_thread);
+    assert_eq!(last_thread, main_thread);
+  }
+}
@@ -0,0 +1,95 @@
+use super::{ApplicationExt, WebviewManager};
+use crate::api::sidecar::{Sidecar, SidecarBuilder, SidecarEvent};
+
+use once_cell::sync::Lazy;
+
+use std::{
+  collections::HashMap,
+  sync::{Mutex, Once},
+  time::Duration,
+};
+
+type SidecarId = u32;
+
+/// Gets the running sidecars.
+fn sidecars() -> &'static Mutex<HashMap<SidecarId, Sidecar>> {
+  static SIDECARS: Lazy<Mutex<HashMap<SidecarId, Sidecar>>> = Lazy::new(Default::default);
+  &SIDECARS
+}
+
+/// The maximum time waited for the sidecars to terminate when the app exits.
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Spawns a sidecar, emitting its events to all webviews and recording its output for the crash reports.
+///
+/// The sidecar is killed when the process exits.
+pub(crate) fn spawn<A: ApplicationExt + 'static>(
+  webview_manager: WebviewManager<A>,
+  builder: SidecarBuilder,
+) -> crate::Result<Sidecar> {
+  static EXIT_HOOK: Once = Once::new();
+  // the webview event loop exits the process without returning from `run`
+  EXIT_HOOK.call_once(|| unsafe {
+    libc::atexit(kill_all);
+  });
+
+  let (mut events, sidecar) = {
+    // the sidecar tasks run on the Tauri runtime, even if spawned outside of it
+    let _runtime = crate::async_runtime::enter();
+    builder.spawn()?
+  };
+  let name = sidecar.name().to_string();
+  let id = rand::random::<SidecarId>();
+  sidecars()
+    .lock()
+    .expect("Failed to lock sidecars: spawn()")
+    .insert(id, sidecar.clone());
+
+  crate::async_runtime::spawn(async move {
+    let event_name = format!("sidecar://{}", name);
+    while let Some(event) = events.recv().await {
+      let line = match &event {
+        SidecarEvent::Stdout(line) | SidecarEvent::Stderr(line) => line.clone(),
+        SidecarEvent::Error(error) => format!("error: {}", error),
+        SidecarEvent::Terminated(payload) => format!(
+          "terminated with code {:?} and signal {:?}",
+          payload.code, payload.signal
+        ),
+        SidecarEvent::Restarted(pid) => format!("restarted with pid {}", pid),
+      };
+      log::info!("[{}] {}", name, line);
+      let _ = webview_manager.emit(&event_name, Some(event)).await;
+    }
+    sidecars()
+      .lock()
+      .expect("Failed to lock sidecars: spawn()")
+      .remove(&id);
+  });
+
+  Ok(sidecar)
+}
+
+/// Kills the running sidecars and waits for them to terminate, registered as a process exit hook.
+///
+/// The runtime threads are still running when the exit hooks are called.
+extern "C" fn kill_all() {
+  // the lock is poisoned if a thread panicked while holding it, and panicking here would abort the process
+  let running: Vec<Sidecar> = match sidecars().lock() {
+    Ok(mut sidecars) => sidecars.drain().map(|(_, sidecar)| sidecar).collect(),
+    Err(_) => return,
+  };
+  if running.is_empty() {
+    return;
+  }
+  // the runtime can't be blocked on if the process exits from one of its tasks
+  if tokio::runtime::Handle::try_current().is_ok() {
+    for sidecar in running {
+      sidecar.kill();
+    }
+    return;
+  }
+  crate::async_runtime::block_on(async move {
+    let stop = futures::future::join_all(running.iter().map(Sidecar::stop));
+    let _ = tokio::time::timeout(EXIT_TIMEOUT, stop).await;
+  });
+}
@@ -7,7 +7,7 @@ use crate::{
     rpc::{format_callback, format_callback_result},
   },
   app::{Icon, InvokeResponse},
-  ApplicationExt, WebviewBuilderExt,
+  ApplicationDispatcherExt, ApplicationExt, WebviewBuilderExt,
 };
 
 use super::{
@@ -91,6 +91,14 @@ pub(super) fn initialization_script(
           window.__TAURI__.invoke({{ cmd: "__initialized" }})
         }})
       }}
+      window.addEventListener('unload', function () {{
+        window.__TAURI__.invoke({{
+          __tauriModule: 'Internal',
+          message: {{
+            cmd: 'unload'
+          }}
+        }})
+      }})
       {plugin_initialization_script}
     "#,
     tauri_initialization_script = tauri_script,
@@ -182,7 +190,7 @@ pub(super) fn build_webview<A: ApplicationExt + 'static>(
     let webview_manager_ = webview_manager.clone();
     let tauri_invoke_handler = crate::Callback::<A::Dispatcher> {
       name: "__TAURI_INVOKE_HANDLER__".to_string(),
-      function: Box::new(move |_, arg| {
+      function: Box::new(move |dispatcher, arg| {
         let arg = arg.into_iter().next().unwrap_or(JsonValue::Null);
         let webview_manager = webview_manager_.clone();
         match serde_json::from_value::<Message>(arg) {
@@ -190,39 +198,29 @@ pub(super) fn build_webview<A: ApplicationExt + 'static>(
             let application = application.clone();
             let callback = message.callback.to_string();
             let error = message.error.to_string();
+            let main_thread = message.main_thread;
 
-            if message.main_thread {
-              crate::async_runtime::block_on(async move {
-                execute_promise(
-                  &webview_manager,
-                  on_message(application, webview_manager.clone(), message),
-                  callback,
-                  error,
-                )
-                .await;
-              });
+            let task = async move {
+              execute_promise(
+                &webview_manager,
+                on_message(application, webview_manager.clone(), message),
+                callback,
+                error,
+              )
+              .await;
+            };
+            if main_thread {
+              super::main_thread::spawn(dispatcher, task);
             } else {
-              crate::async_runtime::spawn(async move {
-                execute_promise(
-                  &webview_manager,
-                  on_message(application, webview_manager.clone(), message),
-                  callback,
-                  error,
-                )
-                .await;
-              });
+              crate::async_runtime::spawn(task);
             }
           }
           Err(e) => {
-            if let Ok(dispatcher) =
-              crate::async_runtime::block_on(webview_manager.current_webview())
-            {
-              let error: crate::Error = e.into();
-              let _ = dispatcher.eval(&format!(
-                r#"console.error({})"#,
-                JsonValue::String(error.to_string())
-              ));
-            }
+            let error: crate::Error = e.into();
+            let _ = dispatcher.eval_script(format!(
+              r#"console.error({})"#,
+              JsonValue::String(error.to_string())
+            ));
           }
         }
       }),
@@ -1,6 +1,6 @@
 pub mod wry;
 
-use crate::plugin::PluginStore;
+use crate::{plugin::PluginStore, SyncTask};
 
 use serde_json::Value as JsonValue;
 
@@ -147,6 +147,14 @@ pub trait WebviewBuilderExt: Sized {
   /// Whether the window should always be on top of other windows.
   fn always_on_top(self, always_on_top: bool) -> Self;
 
+  /// Sets a handler deciding whether the webview can navigate to a URL.
+  ///
+  /// The handler is called for the navigations started with [`ApplicationDispatcherExt::navigate`] and the page links,
+  /// and for every loaded document, including the app URL. A rejected link or `navigate` call doesn't start;
+  /// a rejected document, e.g. from a `window.location` change, is replaced with the last allowed one
+  /// and its calls to the Tauri APIs are ignored.
+  fn on_navigation<F: Fn(&str) -> bool + Send + Sync + 'static>(self, handler: F) -> Self;
+
   /// Sets the window icon.
   fn icon(self, icon: Icon) -> crate::Result<Self>;
 
@@ -251,6 +259,21 @@ pub trait ApplicationDispatcherExt: Clone + Send + Sync + Sized {
 
   /// Evals a script on the webview.
   fn eval_script<S: Into<String>>(&self, script: S) -> crate::Result<()>;
+
+  /// Navigates the webview to the given URL, checking it with the navigation handler set on the webview builder.
+  fn navigate<S: Into<String>>(&self, url: S) -> crate::Result<()>;
+
+  /// Reloads the current page.
+  fn reload(&self) -> crate::Result<()>;
+
+  /// Navigates back in the webview history.
+  fn go_back(&self) -> crate::Result<()>;
+
+  /// Navigates forward in the webview history.
+  fn go_forward(&self) -> crate::Result<()>;
+
+  /// Queues a task to be executed on the main thread by the event loop.
+  fn run_on_main_thread(&self, task: SyncTask) -> crate::Result<()>;
 }
 
 /// The application interface.
@@ -5,13 +5,228 @@ use super::{
 
 use once_cell::sync::Lazy;
 
-use crate::plugin::PluginStore;
+use crate::{plugin::PluginStore, SyncTask};
 
 use std::{
   convert::{TryFrom, TryInto},
   sync::{Arc, Mutex},
 };
 
+/// The name of the callback that runs the tasks queued with `run_on_main_thread`.
+const MAIN_THREAD_HANDLER: &str = "__TAURI_MAIN_THREAD_HANDLER__";
+
+/// The tasks waiting to be executed on the main thread.
+static MAIN_THREAD_TASKS: Lazy<Mutex<Vec<SyncTask>>> = Lazy::new(Default::default);
+
+/// Runs the queued main thread tasks. Must be called on the main thread, i.e. from a webview callback.
+fn run_main_thread_tasks() {
+  let tasks = std::mem::take(
+    &mut *MAIN_THREAD_TASKS
+      .lock()
+      .expect("Failed to lock main thread tasks: run_main_thread_tasks()"),
+  );
+  // the lock is released before running the tasks since they might queue new tasks
+  for task in tasks {
+    task();
+  }
+}
+
+/// The name of the callback that reports the page navigations to the navigation handler.
+const NAVIGATION_HANDLER: &str = "__TAURI_NAVIGATION_HANDLER__";
+
+/// Decides whether the webview can navigate to a URL.
+type NavigationHandler = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// The navigation checks of a window with a navigation handler.
+struct Navigation {
+  handler: NavigationHandler,
+  state: Mutex<NavigationState>,
+}
+
+#[derive(Default)]
+struct NavigationState {
+  /// The URL of the last allowed document.
+  current: Option<String>,
+  /// A navigation allowed by the handler that hasn't loaded yet.
+  approved: Option<String>,
+  /// Whether the loaded document was rejected by the handler.
+  rejected: bool,
+}
+
+impl Navigation {
+  fn state(&self) -> std::sync::MutexGuard<'_, NavigationState> {
+    self.state.lock().expect("Failed to lock navigation state")
+  }
+
+  /// Checks a navigation requested by the app or the page before it starts.
+  fn request(&self, url: &str) -> bool {
+    // the handler is called without the lock since it might navigate the window
+    let allowed = (self.handler)(url);
+    if allowed {
+      self.state().approved = Some(url.to_string());
+    }
+    allowed
+  }
+
+  /// Checks a loaded document, returning the URL to go to if the handler rejects it.
+  fn load(&self, url: &str) -> Option<String> {
+    let approved = self
+      .state()
+      .approved
+      .take()
+      .map_or(false, |approved| approved == url);
+    let allowed = approved || (self.handler)(url);
+    let mut state = self.state();
+    state.rejected = !allowed;
+    if allowed {
+      state.current = Some(url.to_string());
+      None
+    } else {
+      let fallback = state
+        .current
+        .clone()
+        .unwrap_or_else(|| "about:blank".to_string());
+      state.approved = Some(fallback.clone());
+      Some(fallback)
+    }
+  }
+
+  /// Whether the loaded document was rejected, in which case its callbacks are ignored.
+  fn is_rejected(&self) -> bool {
+    self.state().rejected
+  }
+}
+
+/// The script navigating the page to the given URL.
+fn navigation_script(url: &str, replace: bool) -> crate::Result<String> {
+  let url = serde_json::to_string(url)?;
+  if replace {
+    Ok(format!("window.location.replace({})", url))
+  } else {
+    Ok(format!("window.location.href = {}", url))
+  }
+}
+
+/// Reports every loaded document and intercepts the link navigations, which the handler checks before they start.
+fn navigation_initialization_script() -> String {
+  format!(
+    r#"
+      (function () {{
+        var pending = [];
+        var handler = window.{name};
+        function report(kind, url) {{
+          if (handler) {{
+            handler(kind, url);
+          }} else {{
+            pending.push([kind, url]);
+          }}
+        }}
+        // the callback might be bound after this script, so the load is reported as soon as it is
+        Object.defineProperty(window, '{name}', {{
+          configurable: true,
+          get: function () {{
+            return handler;
+          }},
+          set: function (value) {{
+            handler = value;
+            pending.splice(0).forEach(function (args) {{
+              handler(args[0], args[1]);
+            }});
+          }}
+        }});
+        report('load', window.location.href);
+        // registered on the window so the page listeners, e.g. client side routers, can prevent the navigation first
+        window.addEventListener('click', function (e) {{
+          var link = e.target && e.target.closest ? e.target.closest('a[href]') : null;
+          if (e.defaultPrevented || !link || (link.target && link.target !== '_self')) {{
+            return;
+          }}
+          var url = new URL(link.href, window.location.href);
+          var current = new URL(window.location.href);
+          url.hash = '';
+          current.hash = '';
+          if (url.href === current.href) {{
+            return;
+          }}
+          e.preventDefault();
+          report('request', link.href);
+        }});
+      }})();
+    "#,
+    name = NAVIGATION_HANDLER
+  )
+}
+
+/// Wraps the callbacks for wry, adding the callback that the event loop invokes to drain the main thread tasks
+/// and the callback reporting the navigations if the window has a navigation handler.
+///
+/// The wry callbacks are called by the event loop, so the queued tasks are also drained before each callback.
+/// The callbacks of a document rejected by the navigation handler are ignored.
+fn wry_callbacks(
+  callbacks: Vec<Callback<WryDispatcher>>,
+  app_dispatcher: Arc<Mutex<wry::ApplicationProxy>>,
+  navigation: Option<Arc<Navigation>>,
+) -> Vec<wry::Callback> {
+  let mut wry_callbacks = vec![wry::Callback {
+    name: MAIN_THREAD_HANDLER.to_string(),
+    function: Box::new(|_, _, _| {
+      run_main_thread_tasks();
+      Ok(())
+    }),
+  }];
+  if let Some(navigation) = navigation.clone() {
+    wry_callbacks.push(wry::Callback {
+      name: NAVIGATION_HANDLER.to_string(),
+      function: Box::new(move |dispatcher, _, req| {
+        run_main_thread_tasks();
+        let mut args = req.into_iter();
+        if let (Some(serde_json::Value::String(kind)), Some(serde_json::Value::String(url))) =
+          (args.next(), args.next())
+        {
+          let script = match kind.as_str() {
+            "load" => navigation
+              .load(&url)
+              .map(|fallback| navigation_script(&fallback, true)),
+            "request" if navigation.request(&url) => Some(navigation_script(&url, false)),
+            _ => None,
+          };
+          if let Some(Ok(script)) = script {
+            dispatcher.evaluate_script(script)?;
+          }
+        }
+        Ok(())
+      }),
+    });
+  }
+  for mut callback in callbacks {
+    let app_dispatcher = app_dispatcher.clone();
+    let navigation = navigation.clone();
+    wry_callbacks.push(wry::Callback {
+      name: callback.name.to_string(),
+      function: Box::new(move |dispatcher, _, req| {
+        run_main_thread_tasks();
+        if navigation.as_ref().map_or(false, |n| n.is_rejected()) {
+          log::warn!(
+            "ignoring the `{}` call from a document rejected by the navigation handler",
+            callback.name
+          );
+          return Ok(());
+        }
+        (callback.function)(
+          WryDispatcher(
+            Arc::new(Mutex::new(dispatcher)),
+            app_dispatcher.clone(),
+            navigation.clone(),
+          ),
+          req,
+        );
+        Ok(())
+      }),
+    });
+  }
+  wry_callbacks
+}
+
 impl TryFrom<Icon> for wry::Icon {
   type Error = crate::Error;
   fn try_from(icon: Icon) -> Result<Self, Self::Error> {
@@ -27,14 +242,39 @@ impl TryFrom<Icon> for wry::Icon {
   }
 }
 
-impl WebviewBuilderExtPrivate for wry::Attributes {
+/// The wry webview builder, holding the navigation handler that wry doesn't support.
+#[derive(Default)]
+pub struct WryWebviewBuilder {
+  attributes: wry::Attributes,
+  navigation_handler: Option<NavigationHandler>,
+}
+
+impl WryWebviewBuilder {
+  /// Splits the builder into the wry attributes and the navigation checks of the window.
+  fn into_parts(self) -> (wry::Attributes, Option<Arc<Navigation>>) {
+    let mut attributes = self.attributes;
+    let navigation = self.navigation_handler.map(|handler| {
+      // runs before the other scripts so the document is reported before they can invoke anything
+      attributes
+        .initialization_scripts
+        .insert(0, navigation_initialization_script());
+      Arc::new(Navigation {
+        handler,
+        state: Default::default(),
+      })
+    });
+    (attributes, navigation)
+  }
+}
+
+impl WebviewBuilderExtPrivate for WryWebviewBuilder {
   fn url(mut self, url: String) -> Self {
-    self.url.replace(url);
+    self.attributes.url.replace(url);
     self
   }
 }
 
-impl From<WindowConfig> for wry::Attributes {
+impl From<WindowConfig> for WryWebviewBuilder {
   fn from(window_config: WindowConfig) -> Self {
     let mut webview = wry::Attributes::default()
       .title(window_config.0.title.to_string())
@@ -65,12 +305,15 @@ impl From<WindowConfig> for wry::Attributes {
     if let Some(y) = window_config.0.y {
       webview = webview.y(y);
     }
-    webview
+    Self {
+      attributes: webview,
+      navigation_handler: None,
+    }
   }
 }
 
 /// The webview builder.
-impl WebviewBuilderExt for wry::Attributes {
+impl WebviewBuilderExt for WryWebviewBuilder {
   /// The webview object that this builder creates.
   type Webview = Self;
 
@@ -79,97 +322,105 @@ impl WebviewBuilderExt for wry::Attributes {
   }
 
   fn initialization_script(mut self, init: &str) -> Self {
-    self.initialization_scripts.push(init.to_string());
+    self
+      .attributes
+      .initialization_scripts
+      .push(init.to_string());
     self
   }
 
   fn x(mut self, x: f64) -> Self {
-    self.x = Some(x);
+    self.attributes.x = Some(x);
     self
   }
 
   fn y(mut self, y: f64) -> Self {
-    self.y = Some(y);
+    self.attributes.y = Some(y);
     self
   }
 
   fn width(mut self, width: f64) -> Self {
-    self.width = width;
+    self.attributes.width = width;
     self
   }
 
   fn height(mut self, height: f64) -> Self {
-    self.height = height;
+    self.attributes.height = height;
     self
   }
 
   fn min_width(mut self, min_width: f64) -> Self {
-    self.min_width = Some(min_width);
+    self.attributes.min_width = Some(min_width);
     self
   }
 
   fn min_height(mut self, min_height: f64) -> Self {
-    self.min_height = Some(min_height);
+    self.attributes.min_height = Some(min_height);
     self
   }
 
   fn max_width(mut self, max_width: f64) -> Self {
-    self.max_width = Some(max_width);
+    self.attributes.max_width = Some(max_width);
     self
   }
 
   fn max_height(mut self, max_height: f64) -> Self {
-    self.max_height = Some(max_height);
+    self.attributes.max_height = Some(max_height);
     self
   }
 
   fn resizable(mut self, resizable: bool) -> Self {
-    self.resizable = resizable;
+    self.attributes.resizable = resizable;
     self
   }
 
   fn title<S: Into<String>>(mut self, title: S) -> Self {
-    self.title = title.into();
+    self.attributes.title = title.into();
     self
   }
 
   fn fullscreen(mut self, fullscreen: bool) -> Self {
-    self.fullscreen = fullscreen;
+    self.attributes.fullscreen = fullscreen;
     self
   }
 
   fn maximized(mut self, maximized: bool) -> Self {
-    self.maximized = maximized;
+    self.attributes.maximized = maximized;
     self
   }
 
   fn visible(mut self, visible: bool) -> Self {
-    self.visible = visible;
+    self.attributes.visible = visible;
     self
   }
 
   fn transparent(mut self, transparent: bool) -> Self {
-    self.transparent = transparent;
+    self.attributes.transparent = transparent;
     self
   }
 
   fn decorations(mut self, decorations: bool) -> Self {
-    self.decorations = decorations;
+    self.attributes.decorations = decorations;
     self
   }
 
   fn always_on_top(mut self, always_on_top: bool) -> Self {
-    self.always_on_top = always_on_top;
+    self.attributes.always_on_top = always_on_top;
+    self
+  }
+
+  fn on_navigation<F: Fn(&str) -> bool + Send + Sync + 'static>(mut self, handler: F) -> Self {
+    self.navigation_handler = Some(Arc::new(handler));
     self
   }
 
   fn icon(mut self, icon: Icon) -> crate::Result<Self> {
-    self.icon = Some(icon.try_into()?);
+    self.attributes.icon = Some(icon.try_into()?);
     Ok(self)
   }
 
   fn has_icon(&self) -> bool {
-    self.icon.is_some()
+    self.attributes.icon.is_some()
   }
 
   fn finish(self) -> crate::Result<Self::Webview> {
@@ -181,34 +432,20 @@ impl WebviewBuilderExt for wry::Attributes {
 pub struct WryDispatcher(
   Arc<Mutex<wry::WindowProxy>>,
   Arc<Mutex<wry::ApplicationProxy>>,
+  Option<Arc<Navigation>>,
 );
 
 impl ApplicationDispatcherExt for WryDispatcher {
-  type WebviewBuilder = wry::Attributes;
+  type WebviewBuilder = WryWebviewBuilder;
 
   fn create_webview(
     &self,
-    attributes: Self::WebviewBuilder,
+    webview_builder: Self::WebviewBuilder,
     callbacks: Vec<Callback<Self>>,
     custom_protocol: Option<CustomProtocol>,
   ) -> crate::Result<Self> {
-    let mut wry_callbacks = Vec::new();
-    let app_dispatcher = self.1.clone();
-    for mut callback in callbacks {
-      let app_dispatcher = app_dispatcher.clone();
-      let callback = wry::Callback {
-        name: callback.name.to_string(),
-        function: Box::new(move |dispatcher, _, req| {
-          (callback.function)(
-            Self(Arc::new(Mutex::new(dispatcher)), app_dispatcher.clone()),
-            req,
-          );
-          Ok(())
-        }),
-      };
-      wry_callbacks.push(callback);
-    }
-
+    let (attributes, navigation) = webview_builder.into_parts();
+    let wry_callbacks = wry_callbacks(callbacks, self.1.clone(), navigation.clone());
     let window_dispatcher = self
       .1
       .lock()
@@ -225,6 +462,7 @@ impl ApplicationDispatcherExt for WryDispatcher {
     Ok(Self(
       Arc::new(Mutex::new(window_dispatcher)),
       self.1.clone(),
+      navigation,
     ))
   }
 
@@ -416,6 +654,37 @@ impl ApplicationDispatcherExt for WryDispatcher {
       .evaluate_script(script)
       .map_err(|_| crate::Error::FailedToSendMessage)
   }
+
+  fn navigate<S: Into<String>>(&self, url: S) -> crate::Result<()> {
+    let url = url.into();
+    if let Some(navigation) = &self.2 {
+      if !navigation.request(&url) {
+        return Err(crate::Error::NavigationNotAllowed(url));
+      }
+    }
+    self.eval_script(navigation_script(&url, false)?)
+  }
+
+  fn reload(&self) -> crate::Result<()> {
+    self.eval_script("window.location.reload()")
+  }
+
+  fn go_back(&self) -> crate::Result<()> {
+    self.eval_script("window.history.back()")
+  }
+
+  fn go_forward(&self) -> crate::Result<()> {
+    self.eval_script("window.history.forward()")
+  }
+
+  fn run_on_main_thread(&self, task: SyncTask) -> crate::Result<()> {
+    MAIN_THREAD_TASKS
+      .lock()
+      .expect("Failed to lock main thread tasks: run_on_main_thread()")
+      .push(task);
+    // wakes the event loop, which calls the handler on the main thread
+    self.eval_script(format!("window.{}()", MAIN_THREAD_HANDLER))
+  }
 }
 
 /// A wrapper around the wry Application interface.
@@ -424,7 +693,7 @@ pub struct WryApplication {
 }
 
 impl ApplicationExt for WryApplication {
-  type WebviewBuilder = wry::Attributes;
+  type WebviewBuilder = WryWebviewBuilder;
   type Dispatcher = WryDispatcher;
 
   fn plugin_store() -> &'static PluginStore<Self> {
@@ -443,27 +712,13 @@ impl ApplicationExt for WryApplication {
     callbacks: Vec<Callback<Self::Dispatcher>>,
     custom_protocol: Option<CustomProtocol>,
   ) -> crate::Result<Self::Dispatcher> {
-    let mut wry_callbacks = Vec::new();
+    let (attributes, navigation) = webview_builder.finish()?.into_parts();
     let app_dispatcher = Arc::new(Mutex::new(self.inner.application_proxy()));
-    for mut callback in callbacks {
-      let app_dispatcher = app_dispatcher.clone();
-      let callback = wry::Callback {
-        name: callback.name.to_string(),
-        function: Box::new(move |dispatcher, _, req| {
-          (callback.function)(
-            WryDispatcher(Arc::new(Mutex::new(dispatcher)), app_dispatcher.clone()),
-            req,
-          );
-          Ok(())
-        }),
-      };
-      wry_callbacks.push(callback);
-    }
-
+    let wry_callbacks = wry_callbacks(callbacks, app_dispatcher.clone(), navigation.clone());
     let dispatcher = self
       .inner
       .add_window_with_configs(
-        webview_builder.finish()?,
+        attributes,
         Some(wry_callbacks),
         custom_protocol.map(|p| wry::CustomProtocol {
           name: p.name.clone(),
@@ -474,6 +729,7 @@ impl ApplicationExt for WryApplication {
     Ok(WryDispatcher(
       Arc::new(Mutex::new(dispatcher)),
       app_dispatcher,
+      navigation,
     ))
   }
 
@@ -481,3 +737,52 @@ impl ApplicationExt for WryApplication {
     wry::Application::run(self.inner)
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  #[test]
+  fn check_main_thread_tasks() {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    for _ in 0..3 {
+      MAIN_THREAD_TASKS.lock().unwrap().push(Box::new(|| {
+        COUNTER.fetch_add(1, Ordering::SeqCst);
+        // tasks can queue other tasks
+        MAIN_THREAD_TASKS.lock().unwrap().push(Box::new(|| {
+          COUNTER.fetch_add(10, Ordering::SeqCst);
+        }));
+      }));
+    }
+    run_main_thread_tasks();
+    assert_eq!(COUNTER.load(Ordering::SeqCst), 3);
+    run_main_thread_tasks();
+    assert_eq!(COUNTER.load(Ordering::SeqCst), 33);
+    assert!(MAIN_THREAD_TASKS.lock().unwrap().is_empty());
+  }
+
+  #[test]
+  fn check_navigation() {
+    let navigation = Navigation {
+      handler: Arc::new(|url: &str| url.starts_with("tauri://")),
+      state: Default::default(),
+    };
+    assert_eq!(navigation.load("tauri://index.html"), None);
+    assert!(!navigation.is_rejected());
+
+    assert!(navigation.request("tauri://other.html"));
+    assert!(!navigation.request("https://example.com"));
+    assert_eq!(navigation.load("tauri://other.html"), None);
+
+    // a rejected document goes back to the last allowed one and its callbacks are ignored
+    assert_eq!(
+      navigation.load("https://example.com"),
+      Some("tauri://other.html".to_string())
+    );
+    assert!(navigation.is_rejected());
+    assert_eq!(navigation.load("tauri://other.html"), None);
+    assert!(!navigation.is_rejected());
+  }
+}
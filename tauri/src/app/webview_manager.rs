@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use super::{
   App, ApplicationDispatcherExt, ApplicationExt, Icon, Webview, WebviewBuilderExt,
@@ -6,7 +6,7 @@ use super::{
 };
 use crate::{api::config::WindowUrl, async_runtime::Mutex, flavors::Wry};
 
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 
 /// The webview dispatcher.
 #[derive(Clone)]
@@ -56,6 +56,58 @@ impl<A: ApplicationDispatcherExt> WebviewDispatcher<A> {
     self.dispatcher.eval_script(js)
   }
 
+  /// Evaluates a JS script and returns its result.
+  ///
+  /// The script result must be JSON serializable; promises are awaited.
+  /// Requires the window to be loaded from the app URL, since the result is sent through the invoke channel.
+  /// Fails if the result isn't received within 30 seconds.
+  pub async fn eval_with_result<T: DeserializeOwned>(&self, js: &str) -> crate::Result<T> {
+    let (id, rx) = super::eval::register(&self.window_label);
+    if let Err(e) = self.eval(&super::eval::script(&id, js)) {
+      super::eval::unregister(&id);
+      return Err(e);
+    }
+    let value = match tokio::time::timeout(Duration::from_secs(30), rx).await {
+      Ok(result) => result
+        .map_err(|_| crate::Error::EvalScript("the webview didn't send the result".to_string()))?
+        .map_err(crate::Error::EvalScript)?,
+      Err(_) => {
+        super::eval::unregister(&id);
+        return Err(crate::Error::EvalScript(
+          "timed out waiting for the result".to_string(),
+        ));
+      }
+    };
+    serde_json::from_value(value).map_err(Into::into)
+  }
+
+  /// Runs the task on the main thread. The task is queued and executed by the event loop.
+  pub fn run_on_main_thread<F: FnOnce() + Send + 'static>(&self, task: F) -> crate::Result<()> {
+    self.dispatcher.run_on_main_thread(Box::new(task))
+  }
+
+  /// Navigates the webview to the given URL.
+  ///
+  /// Fails if the navigation handler set on the webview builder rejects the URL.
+  pub fn navigate(&self, url: &str) -> crate::Result<()> {
+    self.dispatcher.navigate(url)
+  }
+
+  /// Reloads the current page.
+  pub fn reload(&self) -> crate::Result<()> {
+    self.dispatcher.reload()
+  }
+
+  /// Navigates back in the webview history.
+  pub fn go_back(&self) -> crate::Result<()> {
+    self.dispatcher.go_back()
+  }
+
+  /// Navigates forward in the webview history.
+  pub fn go_forward(&self) -> crate::Result<()> {
+    self.dispatcher.go_forward()
+  }
+
   /// Updates the window resizable flag.
   pub fn set_resizable(&self, resizable: bool) -> crate::Result<()> {
     self.dispatcher.set_resizable(resizable)
@@ -269,6 +321,25 @@ impl<A: ApplicationExt + 'static> WebviewManager<A> {
     Ok(WebviewDispatcher::new(window_dispatcher, label))
   }
 
+  /// Runs the task on the main thread, using the webview associated with the current context to wake the event loop.
+  pub async fn run_on_main_thread<F: FnOnce() + Send + 'static>(
+    &self,
+    task: F,
+  ) -> crate::Result<()> {
+    self.current_webview().await?.run_on_main_thread(task)
+  }
+
+  /// Spawns a sidecar bundled with the `externalBin` config.
+  ///
+  /// Its events are emitted to all webviews as `sidecar://{name}` and its output is recorded for the crash reports.
+  /// The sidecar is killed when the app exits.
+  pub fn spawn_sidecar(
+    &self,
+    builder: crate::api::sidecar::SidecarBuilder,
+  ) -> crate::Result<crate::api::sidecar::Sidecar> {
+    super::sidecar::spawn(self.clone(), builder)
+  }
+
   /// Listen to a global event.
   /// An event from any webview will trigger the handler.
   pub fn listen<F: FnMut(Option<String>) + Send + 'static>(
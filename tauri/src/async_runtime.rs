@@ -1,30 +1,125 @@
 use once_cell::sync::OnceCell;
-use tokio::runtime::Runtime;
-
-use std::sync::Mutex as StdMutex;
+use tauri_api::config::AsyncRuntimeConfig;
+use tokio::runtime::{Builder, EnterGuard, Handle, Runtime};
 
 pub use tokio::sync::Mutex;
 
-static RUNTIME: OnceCell<StdMutex<Runtime>> = OnceCell::new();
+static RUNTIME: OnceCell<GlobalRuntime> = OnceCell::new();
+
+/// The runtime used to run the Tauri async tasks.
+enum GlobalRuntime {
+  /// A multi-threaded runtime owned by Tauri.
+  Tokio(Runtime),
+  /// A runtime owned by the app.
+  Handle(Handle),
+}
+
+impl GlobalRuntime {
+  fn handle(&self) -> Handle {
+    match self {
+      Self::Tokio(runtime) => runtime.handle().clone(),
+      Self::Handle(handle) => handle.clone(),
+    }
+  }
+
+  fn enter(&self) -> EnterGuard<'_> {
+    match self {
+      Self::Tokio(runtime) => runtime.enter(),
+      Self::Handle(handle) => handle.enter(),
+    }
+  }
+
+  fn block_on<F: futures::Future>(&self, task: F) -> F::Output {
+    match self {
+      Self::Tokio(runtime) => runtime.block_on(task),
+      Self::Handle(handle) => handle.block_on(task),
+    }
+  }
+}
+
+fn create_runtime(config: &AsyncRuntimeConfig) -> GlobalRuntime {
+  let mut builder = Builder::new_multi_thread();
+  builder.enable_all();
+  if let Some(worker_threads) = config.worker_threads {
+    builder.worker_threads(worker_threads);
+  }
+  if let Some(max_blocking_threads) = config.max_blocking_threads {
+    builder.max_blocking_threads(max_blocking_threads);
+  }
+  GlobalRuntime::Tokio(builder.build().expect("failed to create async runtime"))
+}
+
+fn runtime() -> &'static GlobalRuntime {
+  RUNTIME.get_or_init(|| create_runtime(&Default::default()))
+}
+
+/// Initializes the global runtime with the given handle,
+/// or with a runtime created with the given config if the handle is `None`.
+///
+/// Without a handle, the runtime is reused if it was already initialized, e.g. by a previous app build.
+/// Fails if a handle is given and the runtime was already initialized, e.g. by a previous call to [`block_on`] or [`spawn`].
+pub(crate) fn init(handle: Option<Handle>, config: &AsyncRuntimeConfig) -> crate::Result<()> {
+  init_runtime(&RUNTIME, handle, config)
+}
+
+fn init_runtime(
+  cell: &OnceCell<GlobalRuntime>,
+  handle: Option<Handle>,
+  config: &AsyncRuntimeConfig,
+) -> crate::Result<()> {
+  match handle {
+    Some(handle) => cell
+      .set(GlobalRuntime::Handle(handle))
+      .map_err(|_| crate::Error::AsyncRuntimeAlreadyInitialized),
+    None => {
+      cell.get_or_init(|| create_runtime(config));
+      Ok(())
+    }
+  }
+}
 
+/// Runs the future to completion on the global runtime, blocking the current thread.
+///
+/// # Panics
+///
+/// Panics if called from an async context, e.g. from a task spawned on the runtime.
 pub fn block_on<F: futures::Future>(task: F) -> F::Output {
-  let runtime = RUNTIME.get_or_init(|| StdMutex::new(Runtime::new().unwrap()));
-  runtime.lock().unwrap().block_on(task)
+  runtime().block_on(task)
+}
+
+/// Enters the global runtime context, so the Tokio tasks spawned until the guard is dropped run on it.
+pub(crate) fn enter() -> EnterGuard<'static> {
+  runtime().enter()
+}
+
+/// The handle of the global runtime.
+pub(crate) fn handle() -> Handle {
+  runtime().handle()
 }
 
+/// Spawns a future onto the global runtime.
 pub fn spawn<F>(task: F)
 where
   F: futures::Future + Send + 'static,
   F::Output: Send + 'static,
 {
-  let runtime = RUNTIME.get_or_init(|| StdMutex::new(Runtime::new().unwrap()));
-  runtime.lock().unwrap().spawn(task);
+  runtime().handle().spawn(task);
 }
 
-pub fn spawn_task<F>(task: F)
-where
-  F: futures::Future + Send + 'static,
-  F::Output: Send + 'static,
-{
-  tokio::spawn(task);
+#[cfg(test)]
+mod test {
+  use super::{init_runtime, GlobalRuntime};
+  use once_cell::sync::OnceCell;
+
+  #[test]
+  fn check_init_twice() {
+    let cell: OnceCell<GlobalRuntime> = OnceCell::new();
+    assert!(init_runtime(&cell, None, &Default::default()).is_ok());
+    assert_eq!(cell.get().unwrap().block_on(async { 1 }), 1);
+    // the runtime is reused when no handle is given
+    assert!(init_runtime(&cell, None, &Default::default()).is_ok());
+    // but it can't be replaced once it's initialized
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    assert!(init_runtime(&cell, Some(runtime.handle().clone()), &Default::default()).is_err());
+  }
 }
@@ -0,0 +1,403 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri_api::{
+  file::{read_string, write_atomic},
+  path::{resolve_path, BaseDirectory},
+};
+
+use std::{
+  collections::VecDeque,
+  fs,
+  panic::{self, PanicInfo},
+  path::{Path, PathBuf},
+  sync::{Arc, Mutex},
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The default number of log lines kept in memory for the crash report.
+const DEFAULT_MAX_LOG_LINES: usize = 100;
+
+/// The directory (relative to the app directory) where the crash reports are saved.
+const CRASH_REPORTS_DIR: &str = "crash-reports";
+
+/// The directory (relative to the crash reports directory) holding reports not yet handled by the app.
+const PENDING_DIR: &str = "pending";
+
+struct LogBuffer {
+  lines: VecDeque<String>,
+  max_lines: usize,
+}
+
+static LOG_BUFFER: Lazy<Mutex<LogBuffer>> = Lazy::new(|| {
+  Mutex::new(LogBuffer {
+    lines: VecDeque::new(),
+    max_lines: DEFAULT_MAX_LOG_LINES,
+  })
+});
+
+/// Records a log line to be included on the next crash report.
+///
+/// The records of the `log` crate are recorded once the [`CrashReporter`] is installed.
+/// Only the last lines are kept, according to [`CrashReporter::max_log_lines`].
+pub fn record_log(line: impl Into<String>) {
+  let mut buffer = LOG_BUFFER
+    .lock()
+    .expect("Failed to lock log buffer: record_log()");
+  buffer.lines.push_back(line.into());
+  while buffer.lines.len() > buffer.max_lines {
+    buffer.lines.pop_front();
+  }
+}
+
+/// The logger installed by the crash reporter.
+/// Records the log lines for the crash report and forwards the records to the app logger.
+struct CrashLogger {
+  level: LevelFilter,
+  logger: Option<Arc<dyn Log>>,
+}
+
+impl Log for CrashLogger {
+  fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+    metadata.level() <= self.level
+      || self
+        .logger
+        .as_ref()
+        .map_or(false, |logger| logger.enabled(metadata))
+  }
+
+  fn log(&self, record: &Record<'_>) {
+    if record.level() <= self.level {
+      record_log(format!(
+        "[{}] {}: {}",
+        record.level(),
+        record.target(),
+        record.args()
+      ));
+    }
+    if let Some(logger) = &self.logger {
+      if logger.enabled(record.metadata()) {
+        logger.log(record);
+      }
+    }
+  }
+
+  fn flush(&self) {
+    if let Some(logger) = &self.logger {
+      logger.flush();
+    }
+  }
+}
+
+/// The app logger wrapped by the crash reporter.
+#[derive(Clone)]
+struct AppLogger(Arc<dyn Log>);
+
+impl std::fmt::Debug for AppLogger {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("AppLogger").finish()
+  }
+}
+
+/// The crash report written when the app panics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+  /// The report identifier.
+  pub id: String,
+  /// The time of the crash, in seconds since the Unix epoch.
+  pub timestamp: u64,
+  /// The panic message.
+  pub message: String,
+  /// The source location of the panic.
+  pub location: Option<String>,
+  /// The name of the thread that panicked.
+  pub thread: Option<String>,
+  /// The captured backtrace.
+  pub backtrace: String,
+  /// The app version, if set on the [`CrashReporter`].
+  pub app_version: Option<String>,
+  /// The operating system.
+  pub os: String,
+  /// The CPU architecture.
+  pub arch: String,
+  /// The last recorded log lines.
+  pub log: Vec<String>,
+  #[serde(skip)]
+  path: PathBuf,
+}
+
+impl CrashReport {
+  /// The path to the report file.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  fn from_panic(info: &PanicInfo<'_>, app_version: Option<String>) -> Self {
+    let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+      message.to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+      message.clone()
+    } else {
+      "Box<Any>".to_string()
+    };
+    // the panic might have happened while the log buffer was locked
+    let log = LOG_BUFFER
+      .try_lock()
+      .map(|buffer| buffer.lines.iter().cloned().collect())
+      .unwrap_or_default();
+
+    Self {
+      id: uuid::Uuid::new_v4().to_string(),
+      timestamp: SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default(),
+      message,
+      location: info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+      thread: std::thread::current().name().map(Into::into),
+      backtrace: format!("{:?}", backtrace::Backtrace::new()),
+      app_version,
+      os: std::env::consts::OS.to_string(),
+      arch: std::env::consts::ARCH.to_string(),
+      log,
+      path: PathBuf::new(),
+    }
+  }
+}
+
+/// The crash reporter builder.
+/// Installs a panic hook that saves a [`CrashReport`] to the app directory,
+/// and a logger recording the last lines logged with the `log` crate.
+///
+/// # Example
+/// ```no_run
+/// use tauri::crash::CrashReporter;
+/// CrashReporter::new()
+///   .app_version(env!("CARGO_PKG_VERSION"))
+///   .install()
+///   .expect("failed to install the crash reporter");
+/// log::info!("recorded on the crash reports");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CrashReporter {
+  app_version: Option<String>,
+  max_log_lines: Option<usize>,
+  log_level: Option<LevelFilter>,
+  logger: Option<AppLogger>,
+  directory: Option<PathBuf>,
+}
+
+impl CrashReporter {
+  /// Creates a new crash reporter with the default options.
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Sets the app version included on the report.
+  pub fn app_version(mut self, app_version: impl Into<String>) -> Self {
+    self.app_version = Some(app_version.into());
+    self
+  }
+
+  /// Sets the number of log lines recorded with [`record_log`] kept for the report.
+  pub fn max_log_lines(mut self, max_log_lines: usize) -> Self {
+    self.max_log_lines = Some(max_log_lines);
+    self
+  }
+
+  /// Sets the maximum level of the log records kept for the report. Defaults to [`LevelFilter::Info`].
+  pub fn log_level(mut self, log_level: LevelFilter) -> Self {
+    self.log_level = Some(log_level);
+    self
+  }
+
+  /// Sets the app logger. Since only one logger can be set,
+  /// the app logger must be passed here instead of being set with `log::set_logger`.
+  /// The log records are forwarded to it after being recorded for the report.
+  pub fn logger(mut self, logger: impl Log + 'static) -> Self {
+    self.logger = Some(AppLogger(Arc::new(logger)));
+    self
+  }
+
+  /// Sets the directory where the reports are saved. Defaults to `${APP_DIR}/crash-reports`.
+  pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+    self.directory = Some(directory.into());
+    self
+  }
+
+  /// The directory where the reports are saved.
+  pub fn reports_dir(&self) -> crate::Result<PathBuf> {
+    match &self.directory {
+      Some(directory) => Ok(directory.clone()),
+      None => resolve_path(CRASH_REPORTS_DIR, Some(BaseDirectory::App)).map_err(Into::into),
+    }
+  }
+
+  /// Installs the panic hook and the logger.
+  /// The previously installed hook is still called after the report is saved.
+  ///
+  /// Fails if a logger was already set. Use [`CrashReporter::logger`] to set the app logger instead.
+  pub fn install(self) -> crate::Result<()> {
+    let pending_dir = self.reports_dir()?.join(PENDING_DIR);
+    if let Some(max_log_lines) = self.max_log_lines {
+      LOG_BUFFER
+        .lock()
+        .expect("Failed to lock log buffer: install()")
+        .max_lines = max_log_lines;
+    }
+
+    let logger = CrashLogger {
+      level: self.log_level.unwrap_or(LevelFilter::Info),
+      logger: self.logger.map(|logger| logger.0),
+    };
+    // the level of the app logger is unknown, so it must receive all records
+    let max_level = if logger.logger.is_some() {
+      LevelFilter::Trace
+    } else {
+      logger.level
+    };
+    log::set_boxed_logger(Box::new(logger))?;
+    log::set_max_level(max_level);
+
+    let app_version = self.app_version;
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+      let report = CrashReport::from_panic(info, app_version.clone());
+      let report_path = pending_dir.join(format!("{}-{}.json", report.timestamp, report.id));
+      match serde_json::to_string_pretty(&report) {
+        Ok(contents) => {
+          if let Err(e) = write_atomic(&report_path, contents) {
+            log::error!("failed to write crash report: {}", e);
+          }
+        }
+        Err(e) => log::error!("failed to serialize crash report: {}", e),
+      }
+      previous_hook(info);
+    }));
+    Ok(())
+  }
+
+  /// Takes the reports saved by previous runs that weren't handled yet, oldest first.
+  ///
+  /// The reports are moved out of the pending directory, so they're only returned once.
+  /// The reports that can't be read, e.g. if the app was killed while writing them, are returned as errors.
+  /// The reports that can't be moved are skipped and left on the pending directory.
+  pub fn take_pending_reports(&self) -> crate::Result<Vec<crate::Result<CrashReport>>> {
+    let reports_dir = self.reports_dir()?;
+    let pending_dir = reports_dir.join(PENDING_DIR);
+    if !pending_dir.exists() {
+      return Ok(Vec::new());
+    }
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(&pending_dir)? {
+      let path = entry?.path();
+      if path.extension().map_or(false, |ext| ext == "json") {
+        paths.push(path);
+      }
+    }
+
+    let mut reports = Vec::new();
+    for path in paths {
+      let file_name = path.file_name().expect("report without file name");
+      let handled_path = reports_dir.join(file_name);
+      let report = read_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+          serde_json::from_str::<CrashReport>(&contents).map_err(|e| e.to_string())
+        });
+      if let Err(e) = fs::rename(&path, &handled_path) {
+        log::warn!("failed to move crash report {}: {}", path.display(), e);
+        continue;
+      }
+      // the file names start with the crash timestamp, used to sort the reports that can't be read
+      let timestamp = match &report {
+        Ok(report) => report.timestamp,
+        Err(_) => file_name
+          .to_string_lossy()
+          .split('-')
+          .next()
+          .and_then(|timestamp| timestamp.parse().ok())
+          .unwrap_or(u64::MAX),
+      };
+      reports.push((
+        timestamp,
+        match report {
+          Ok(mut report) => {
+            report.path = handled_path;
+            Ok(report)
+          }
+          Err(e) => Err(crate::Error::InvalidCrashReport(handled_path, e)),
+        },
+      ));
+    }
+    reports.sort_by_key(|(timestamp, _)| *timestamp);
+    Ok(reports.into_iter().map(|(_, report)| report).collect())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn check_log_buffer() {
+    for i in 0..DEFAULT_MAX_LOG_LINES + 10 {
+      record_log(format!("line {}", i));
+    }
+    let buffer = LOG_BUFFER.lock().unwrap();
+    assert_eq!(buffer.lines.len(), DEFAULT_MAX_LOG_LINES);
+    assert_eq!(
+      buffer.lines.back(),
+      Some(&format!("line {}", DEFAULT_MAX_LOG_LINES + 9))
+    );
+  }
+
+  #[test]
+  fn check_pending_reports() {
+    let directory = std::env::temp_dir()
+      .join("tauri-crash-test")
+      .join(uuid::Uuid::new_v4().to_string());
+    let reporter = CrashReporter::new().directory(&directory);
+    assert!(reporter.take_pending_reports().unwrap().is_empty());
+
+    let report = CrashReport {
+      id: "report".to_string(),
+      timestamp: 9,
+      message: "panicked".to_string(),
+      location: None,
+      thread: Some("main".to_string()),
+      backtrace: String::new(),
+      app_version: Some("1.0.0".to_string()),
+      os: std::env::consts::OS.to_string(),
+      arch: std::env::consts::ARCH.to_string(),
+      log: vec!["starting".to_string()],
+      path: PathBuf::new(),
+    };
+    write_atomic(
+      directory.join(PENDING_DIR).join("9-report.json"),
+      serde_json::to_string(&report).unwrap(),
+    )
+    .unwrap();
+
+    // sorted by timestamp, not by file name
+    write_atomic(directory.join(PENDING_DIR).join("10-invalid.json"), "{").unwrap();
+
+    let reports = reporter.take_pending_reports().unwrap();
+    assert_eq!(reports.len(), 2);
+    let valid = reports[0].as_ref().unwrap();
+    assert_eq!(valid.message, report.message);
+    assert_eq!(valid.path(), directory.join("9-report.json"));
+    assert!(matches!(
+      &reports[1],
+      Err(crate::Error::InvalidCrashReport(path, _)) if path == &directory.join("10-invalid.json")
+    ));
+    // the report is only returned once
+    assert!(reporter.take_pending_reports().unwrap().is_empty());
+
+    fs::remove_dir_all(directory).unwrap();
+  }
+}
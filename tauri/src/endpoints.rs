@@ -1,4 +1,5 @@
 mod cli;
+mod clipboard;
 mod dialog;
 mod event;
 #[allow(unused_imports)]
@@ -8,6 +9,8 @@ mod http;
 mod internal;
 mod notification;
 mod shell;
+mod store;
+mod websocket;
 mod window;
 
 use crate::{
@@ -31,6 +34,9 @@ enum Module {
   Notification(notification::Cmd),
   Http(http::Cmd),
   GlobalShortcut(global_shortcut::Cmd),
+  Store(store::Cmd),
+  Clipboard(clipboard::Cmd),
+  Websocket(websocket::Cmd),
 }
 
 impl Module {
@@ -40,16 +46,19 @@ impl Module {
     context: &Context,
   ) -> crate::Result<InvokeResponse> {
     match self {
-      Self::Fs(cmd) => cmd.run().await,
-      Self::Window(cmd) => cmd.run(webview_manager).await,
-      Self::Shell(cmd) => cmd.run().await,
+      Self::Fs(cmd) => cmd.run(webview_manager).await,
+      Self::Window(cmd) => cmd.run(webview_manager, context).await,
+      Self::Shell(cmd) => cmd.run(webview_manager, context).await,
       Self::Event(cmd) => cmd.run(webview_manager).await,
-      Self::Internal(cmd) => cmd.run().await,
+      Self::Internal(cmd) => cmd.run(webview_manager).await,
       Self::Dialog(cmd) => cmd.run().await,
       Self::Cli(cmd) => cmd.run(context).await,
       Self::Notification(cmd) => cmd.run(context).await,
-      Self::Http(cmd) => cmd.run().await,
+      Self::Http(cmd) => cmd.run(webview_manager, context).await,
       Self::GlobalShortcut(cmd) => cmd.run(webview_manager).await,
+      Self::Store(cmd) => cmd.run().await,
+      Self::Clipboard(cmd) => cmd.run().await,
+      Self::Websocket(cmd) => cmd.run(webview_manager, context).await,
     }
   }
 }
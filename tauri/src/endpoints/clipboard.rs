@@ -0,0 +1,39 @@
+#[cfg(any(clipboard_read, clipboard_write))]
+use crate::api::clipboard;
+use crate::app::InvokeResponse;
+use serde::Deserialize;
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// Reads the clipboard content as plain text.
+  ReadText,
+  /// Writes plain text to the clipboard.
+  WriteText { text: String },
+}
+
+impl Cmd {
+  pub async fn run(self) -> crate::Result<InvokeResponse> {
+    match self {
+      Self::ReadText => {
+        #[cfg(clipboard_read)]
+        return clipboard::read_text().map(Into::into).map_err(Into::into);
+        #[cfg(not(clipboard_read))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "clipboard > read".to_string(),
+        ))
+      }
+      Self::WriteText { text } => {
+        #[cfg(clipboard_write)]
+        return clipboard::write_text(text)
+          .map(Into::into)
+          .map_err(Into::into);
+        #[cfg(not(clipboard_write))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "clipboard > write".to_string(),
+        ))
+      }
+    }
+  }
+}
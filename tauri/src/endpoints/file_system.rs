@@ -1,10 +1,61 @@
+#[cfg(any(fs_watch, fs_open))]
+use crate::async_runtime::Mutex;
 use crate::{api::path::BaseDirectory, app::InvokeResponse, ApplicationDispatcherExt};
 
+#[cfg(any(fs_watch, fs_open))]
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use tauri_api::{dir, file, path::resolve_path};
+#[cfg(fs_open)]
+use tauri_api::file::FileHandle;
+#[cfg(fs_watch)]
+use tauri_api::watcher::{self, Watcher};
+use tauri_api::{
+  dir,
+  file::{self, SeekFrom},
+  path::resolve_path,
+};
 
+#[cfg(fs_watch)]
+use std::time::Duration;
+#[cfg(any(fs_watch, fs_open))]
+use std::{collections::HashMap, sync::Arc};
 use std::{fs, fs::File, io::Write, path::PathBuf};
 
+type FileId = u32;
+#[cfg(fs_open)]
+type FileStore = Arc<Mutex<HashMap<FileId, OwnedFile>>>;
+
+/// An open file and the label of the window that opened it.
+#[cfg(fs_open)]
+struct OwnedFile {
+  window_label: String,
+  handle: Arc<Mutex<FileHandle>>,
+}
+
+#[cfg(fs_open)]
+fn files() -> &'static FileStore {
+  static STORE: Lazy<FileStore> = Lazy::new(Default::default);
+  &STORE
+}
+
+type WatcherId = u32;
+#[cfg(fs_watch)]
+type WatcherStore = Arc<Mutex<HashMap<WatcherId, OwnedWatcher>>>;
+
+/// A file system watcher and the label of the window that started it.
+#[cfg(fs_watch)]
+struct OwnedWatcher {
+  window_label: String,
+  /// Stops watching the path when dropped.
+  _watcher: Watcher,
+}
+
+#[cfg(fs_watch)]
+fn watchers() -> &'static WatcherStore {
+  static STORE: Lazy<WatcherStore> = Lazy::new(Default::default);
+  &STORE
+}
+
 /// The options for the directory functions on the file system API.
 #[derive(Deserialize)]
 pub struct DirOperationOptions {
@@ -24,6 +75,35 @@ pub struct FileOperationOptions {
   pub dir: Option<BaseDirectory>,
 }
 
+/// The options for the open API.
+#[derive(Deserialize)]
+pub struct OpenOptions {
+  /// The modes the file is opened with.
+  #[serde(flatten)]
+  pub mode: file::OpenOptions,
+  /// The base directory of the operation.
+  /// The directory path of the BaseDirectory will be the prefix of the defined file path.
+  pub dir: Option<BaseDirectory>,
+}
+
+fn default_debounce_ms() -> u64 {
+  100
+}
+
+/// The options for the watch API.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchOptions {
+  /// Whether the subdirectories should be watched too.
+  #[serde(default)]
+  pub recursive: bool,
+  /// The time without changes to a path before its last change is sent, in milliseconds.
+  #[serde(default = "default_debounce_ms")]
+  pub debounce_ms: u64,
+  /// The base directory of the watched path.
+  pub dir: Option<BaseDirectory>,
+}
+
 /// The API descriptor.
 #[derive(Deserialize)]
 #[serde(tag = "cmd", rename_all = "camelCase")]
@@ -88,10 +168,48 @@ pub enum Cmd {
     path: String,
     directory: Option<BaseDirectory>,
   },
+  /// Watches a path for changes, resolving to the watcher ID.
+  /// The changes are sent to the `on_event` callback.
+  #[serde(rename_all = "camelCase")]
+  Watch {
+    path: PathBuf,
+    options: WatchOptions,
+    on_event: String,
+  },
+  /// Stops a watcher.
+  Unwatch { id: WatcherId },
+  /// Opens a file, resolving to the file ID.
+  Open {
+    path: PathBuf,
+    options: Option<OpenOptions>,
+  },
+  /// Reads up to `len` bytes of an open file, from the offset or from the cursor position, resolving to the base64 encoded bytes.
+  Read {
+    id: FileId,
+    len: usize,
+    offset: Option<u64>,
+  },
+  /// Writes the base64 encoded contents to an open file, at the offset or at the cursor position.
+  Write {
+    id: FileId,
+    contents: String,
+    offset: Option<u64>,
+  },
+  /// Moves the cursor of an open file, resolving to its new position.
+  Seek { id: FileId, from: SeekFrom },
+  /// Truncates or extends an open file.
+  Truncate { id: FileId, len: u64 },
+  /// Flushes an open file to the disk.
+  Sync { id: FileId },
+  /// Closes a file.
+  Close { id: FileId },
 }
 
 impl Cmd {
-  pub async fn run(self) -> crate::Result<InvokeResponse> {
+  pub async fn run<A: crate::ApplicationExt + 'static>(
+    self,
+    webview_manager: &crate::WebviewManager<A>,
+  ) -> crate::Result<InvokeResponse> {
     match self {
       Self::ReadTextFile { path, options } => {
         #[cfg(fs_read_text_file)]
@@ -197,6 +315,81 @@ impl Cmd {
         #[cfg(not(fs_path))]
         Err(crate::Error::ApiNotAllowlisted("fs > pathApi".to_string()))
       }
+      Self::Watch {
+        path,
+        options,
+        on_event,
+      } => {
+        #[cfg(fs_watch)]
+        return watch(webview_manager, path, options, on_event)
+          .await
+          .map(Into::into);
+        #[cfg(not(fs_watch))]
+        Err(crate::Error::ApiNotAllowlisted("fs > watch".to_string()))
+      }
+      Self::Unwatch { id } => {
+        #[cfg(fs_watch)]
+        return unwatch(webview_manager, id).await.map(Into::into);
+        #[cfg(not(fs_watch))]
+        Err(crate::Error::ApiNotAllowlisted("fs > watch".to_string()))
+      }
+      Self::Open { path, options } => {
+        #[cfg(fs_open)]
+        return open(webview_manager, path, options).await.map(Into::into);
+        #[cfg(not(fs_open))]
+        Err(crate::Error::ApiNotAllowlisted("fs > open".to_string()))
+      }
+      Self::Read { id, len, offset } => {
+        #[cfg(fs_open)]
+        return with_file(webview_manager, id, |file| file.read(len, offset))
+          .await
+          .map(|data| base64::encode(&data))
+          .map(Into::into);
+        #[cfg(not(fs_open))]
+        Err(crate::Error::ApiNotAllowlisted("fs > open".to_string()))
+      }
+      Self::Write {
+        id,
+        contents,
+        offset,
+      } => {
+        #[cfg(fs_open)]
+        return write(webview_manager, id, contents, offset)
+          .await
+          .map(Into::into);
+        #[cfg(not(fs_open))]
+        Err(crate::Error::ApiNotAllowlisted("fs > open".to_string()))
+      }
+      Self::Seek { id, from } => {
+        #[cfg(fs_open)]
+        return with_file(webview_manager, id, |file| file.seek(from))
+          .await
+          .map(Into::into);
+        #[cfg(not(fs_open))]
+        Err(crate::Error::ApiNotAllowlisted("fs > open".to_string()))
+      }
+      Self::Truncate { id, len } => {
+        #[cfg(fs_open)]
+        return with_file(webview_manager, id, |file| file.truncate(len))
+          .await
+          .map(Into::into);
+        #[cfg(not(fs_open))]
+        Err(crate::Error::ApiNotAllowlisted("fs > open".to_string()))
+      }
+      Self::Sync { id } => {
+        #[cfg(fs_open)]
+        return with_file(webview_manager, id, |file| file.sync())
+          .await
+          .map(Into::into);
+        #[cfg(not(fs_open))]
+        Err(crate::Error::ApiNotAllowlisted("fs > open".to_string()))
+      }
+      Self::Close { id } => {
+        #[cfg(fs_open)]
+        return close(webview_manager, id).await.map(Into::into);
+        #[cfg(not(fs_open))]
+        Err(crate::Error::ApiNotAllowlisted("fs > open".to_string()))
+      }
     }
   }
 }
@@ -355,6 +548,169 @@ pub async fn resolve_path_handler(
   resolve_path(path, directory).map_err(Into::into)
 }
 
+/// Watches a path, sending its changes to the `on_event` callback of the current webview.
+///
+/// The watchers are stopped when their window is closed or its page is unloaded.
+#[cfg(fs_watch)]
+async fn watch<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  path: PathBuf,
+  options: WatchOptions,
+  on_event: String,
+) -> crate::Result<WatcherId> {
+  let path = resolve_path(path, options.dir)?;
+  let (mut events, watcher) = watcher::watch(
+    path,
+    options.recursive,
+    Duration::from_millis(options.debounce_ms),
+  )?;
+
+  let dispatcher = webview_manager.current_webview().await?;
+  let window_label = webview_manager.current_window_label().to_string();
+  crate::event::on_window_destroyed("fs-watchers", |window_label| {
+    let window_label = window_label.to_string();
+    crate::async_runtime::spawn(async move {
+      watchers()
+        .lock()
+        .await
+        .retain(|_, watcher| watcher.window_label != window_label);
+    });
+  });
+  let mut store = watchers().lock().await;
+  let id = loop {
+    let id = rand::random::<WatcherId>();
+    if !store.contains_key(&id) {
+      break id;
+    }
+  };
+  store.insert(
+    id,
+    OwnedWatcher {
+      window_label,
+      _watcher: watcher,
+    },
+  );
+  // the events end once the watcher is dropped
+  crate::async_runtime::spawn(async move {
+    while let Some(event) = events.recv().await {
+      if let Ok(event) = serde_json::to_value(event) {
+        let _ = dispatcher.eval(&tauri_api::rpc::format_callback(&on_event, event));
+      }
+    }
+  });
+
+  Ok(id)
+}
+
+/// Stops a watcher started by the current window.
+#[cfg(fs_watch)]
+async fn unwatch<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: WatcherId,
+) -> crate::Result<()> {
+  let mut store = watchers().lock().await;
+  match store.get(&id) {
+    Some(watcher) if watcher.window_label == webview_manager.current_window_label() => {
+      store.remove(&id);
+      Ok(())
+    }
+    _ => Err(crate::Error::WatcherNotFound),
+  }
+}
+
+/// Opens a file owned by the current window.
+///
+/// The files are closed when their window is closed or its page is unloaded.
+#[cfg(fs_open)]
+async fn open<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  path: PathBuf,
+  options: Option<OpenOptions>,
+) -> crate::Result<FileId> {
+  let (mode, dir) = match options {
+    Some(options) => (options.mode, options.dir),
+    None => (Default::default(), None),
+  };
+  let handle = FileHandle::open(resolve_path(path, dir)?, &mode)?;
+
+  let window_label = webview_manager.current_window_label().to_string();
+  crate::event::on_window_destroyed("fs-files", |window_label| {
+    let window_label = window_label.to_string();
+    crate::async_runtime::spawn(async move {
+      files()
+        .lock()
+        .await
+        .retain(|_, file| file.window_label != window_label);
+    });
+  });
+  let mut store = files().lock().await;
+  let id = loop {
+    let id = rand::random::<FileId>();
+    if !store.contains_key(&id) {
+      break id;
+    }
+  };
+  store.insert(
+    id,
+    OwnedFile {
+      window_label,
+      handle: Arc::new(Mutex::new(handle)),
+    },
+  );
+
+  Ok(id)
+}
+
+/// Runs the operation on a file opened by the current window.
+#[cfg(fs_open)]
+async fn with_file<
+  A: crate::ApplicationExt + 'static,
+  T,
+  F: FnOnce(&mut FileHandle) -> tauri_api::Result<T>,
+>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: FileId,
+  f: F,
+) -> crate::Result<T> {
+  let handle = files()
+    .lock()
+    .await
+    .get(&id)
+    .filter(|file| file.window_label == webview_manager.current_window_label())
+    .map(|file| file.handle.clone())
+    .ok_or(crate::Error::FileNotOpen)?;
+  let mut handle = handle.lock().await;
+  f(&mut handle).map_err(Into::into)
+}
+
+/// Writes the base64 encoded contents to a file opened by the current window.
+#[cfg(fs_open)]
+async fn write<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: FileId,
+  contents: String,
+  offset: Option<u64>,
+) -> crate::Result<usize> {
+  let contents = base64::decode(contents).map_err(crate::Error::Base64Decode)?;
+  with_file(webview_manager, id, |file| file.write(&contents, offset)).await
+}
+
+/// Closes a file opened by the current window.
+#[cfg(fs_open)]
+async fn close<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: FileId,
+) -> crate::Result<()> {
+  let mut store = files().lock().await;
+  match store.get(&id) {
+    Some(file) if file.window_label == webview_manager.current_window_label() => {
+      store.remove(&id);
+      Ok(())
+    }
+    _ => Err(crate::Error::FileNotOpen),
+  }
+}
+
 // test webview functionality.
 #[cfg(test)]
 mod test {
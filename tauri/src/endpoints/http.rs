@@ -1,73 +1,649 @@
-use crate::{app::InvokeResponse, async_runtime::Mutex};
+use crate::{
+  app::{Context, InvokeResponse},
+  async_runtime::Mutex,
+};
 
+#[cfg(http_request)]
+use futures::{future::AbortHandle, StreamExt};
 use once_cell::sync::Lazy;
 use serde::Deserialize;
-use tauri_api::http::{Client, ClientBuilder, HttpRequestBuilder, ResponseData};
+#[cfg(http_request)]
+use serde::Serialize;
+#[cfg(http_request)]
+use tauri_api::http::{Body, ResponseData};
+#[cfg(http_download)]
+use tauri_api::http::{DownloadHandle, DownloadStatus};
+use tauri_api::{
+  http::{Client, ClientBuilder, DownloadRequestBuilder, HttpRequestBuilder},
+  path::BaseDirectory,
+};
+#[cfg(http_request)]
+use tokio::sync::Semaphore;
 
 use std::{collections::HashMap, sync::Arc};
 
 type ClientId = u32;
-type ClientStore = Arc<Mutex<HashMap<ClientId, Client>>>;
+type ClientStore = Arc<Mutex<HashMap<ClientId, OwnedClient>>>;
+type DownloadId = u32;
+#[cfg(http_download)]
+type DownloadStore = Arc<Mutex<HashMap<DownloadId, OwnedDownload>>>;
+type StreamId = u32;
+#[cfg(http_request)]
+type StreamStore = Arc<Mutex<HashMap<StreamId, ResponseStream>>>;
+
+/// The number of body chunks sent to the webview before waiting for their acknowledgement.
+#[cfg(http_request)]
+const STREAM_CHUNK_WINDOW: usize = 8;
+
+/// A HTTP client and the label of the window that created it.
+struct OwnedClient {
+  window_label: String,
+  #[cfg_attr(not(any(http_request, http_download)), allow(dead_code))]
+  client: Client,
+}
 
 fn clients() -> &'static ClientStore {
   static STORE: Lazy<ClientStore> = Lazy::new(Default::default);
   &STORE
 }
 
+/// A download created by a window.
+#[cfg(http_download)]
+struct OwnedDownload {
+  window_label: String,
+  handle: DownloadHandle,
+  /// Whether the download command is running or not.
+  started: bool,
+}
+
+#[cfg(http_download)]
+fn downloads() -> &'static DownloadStore {
+  static STORE: Lazy<DownloadStore> = Lazy::new(Default::default);
+  &STORE
+}
+
+/// A response body streamed to the window that created the stream.
+#[cfg(http_request)]
+struct ResponseStream {
+  window_label: String,
+  /// One permit for each chunk the webview is ready to receive.
+  permits: Arc<Semaphore>,
+  /// Whether the request streaming the body was made or not.
+  started: bool,
+  /// Aborts the body streaming, set once the response is received.
+  abort_handle: Option<AbortHandle>,
+}
+
+#[cfg(http_request)]
+impl ResponseStream {
+  fn abort(&self) {
+    if let Some(abort_handle) = &self.abort_handle {
+      abort_handle.abort();
+    }
+  }
+}
+
+#[cfg(http_request)]
+fn streams() -> &'static StreamStore {
+  static STORE: Lazy<StreamStore> = Lazy::new(Default::default);
+  &STORE
+}
+
+/// The options for streaming the response body instead of reading it.
+#[derive(Deserialize)]
+#[cfg_attr(not(http_request), allow(dead_code))]
+#[serde(rename_all = "camelCase")]
+pub struct StreamOptions {
+  /// The stream identifier returned by the `createStream` command.
+  id: StreamId,
+  /// The callback receiving the stream events.
+  on_event: String,
+}
+
+/// The events sent to the stream callback.
+#[cfg(http_request)]
+#[derive(Serialize)]
+#[serde(tag = "type", content = "payload", rename_all = "camelCase")]
+enum StreamEvent {
+  /// A base64 encoded body chunk, which must be acknowledged before more than `STREAM_CHUNK_WINDOW` chunks are sent.
+  Chunk(String),
+  /// The body was fully read.
+  End,
+  /// Reading the body failed.
+  Error(String),
+  /// The stream was cancelled before the body was fully read.
+  Cancelled,
+}
+
+/// The options for the download API.
+#[derive(Deserialize)]
+#[cfg_attr(not(http_download), allow(dead_code))]
+pub struct DownloadOptions {
+  #[serde(flatten)]
+  request: DownloadRequestBuilder,
+  /// The base directory of the download path.
+  /// The directory path of the BaseDirectory will be the prefix of the defined file path.
+  dir: Option<BaseDirectory>,
+}
+
 /// The API descriptor.
 #[derive(Deserialize)]
 #[serde(tag = "cmd", rename_all = "camelCase")]
 pub enum Cmd {
   /// Create a new HTTP client.
-  CreateClient { options: Option<ClientBuilder> },
+  CreateClient { options: Option<Box<ClientBuilder>> },
   /// Drop a HTTP client.
   DropClient { client: ClientId },
   /// The HTTP request API.
+  #[serde(rename_all = "camelCase")]
   HttpRequest {
     client: ClientId,
     options: Box<HttpRequestBuilder>,
+    on_upload_progress: Option<String>,
+    /// Streams the response body instead of reading it.
+    stream: Option<StreamOptions>,
+  },
+  /// Creates a response stream owned by the current window, resolving to its id.
+  CreateStream,
+  /// Acknowledges a chunk of a streamed response body, allowing the next one to be sent.
+  AckStreamChunk { id: StreamId },
+  /// Cancels a streamed response body, aborting the request.
+  CancelStream { id: StreamId },
+  /// Creates a download owned by the current window, resolving to its id.
+  CreateDownload,
+  /// Downloads a file, resolving to the download status once it finishes.
+  #[serde(rename_all = "camelCase")]
+  Download {
+    id: DownloadId,
+    client: ClientId,
+    options: Box<DownloadOptions>,
+    on_progress: Option<String>,
   },
+  /// Pauses a download, keeping the partial file.
+  PauseDownload { id: DownloadId },
+  /// Cancels a download, removing the partial file.
+  CancelDownload { id: DownloadId },
 }
 
 impl Cmd {
-  pub async fn run(self) -> crate::Result<InvokeResponse> {
+  #[allow(unused_variables)]
+  pub async fn run<A: crate::ApplicationExt + 'static>(
+    self,
+    webview_manager: &crate::WebviewManager<A>,
+    context: &Context,
+  ) -> crate::Result<InvokeResponse> {
     match self {
       Self::CreateClient { options } => {
-        let client = options.unwrap_or_default().build()?;
-        let mut store = clients().lock().await;
-        let id = rand::random::<ClientId>();
-        store.insert(id, client);
-        Ok(id.into())
+        #[cfg(any(http_request, http_download))]
+        return create_client(
+          webview_manager,
+          context,
+          options.map(|o| *o).unwrap_or_default(),
+        )
+        .await
+        .map(Into::into);
+        #[cfg(not(any(http_request, http_download)))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > request".to_string(),
+        ))
       }
       Self::DropClient { client } => {
         let mut store = clients().lock().await;
-        store.remove(&client);
+        if let Some(owned_client) = store.get(&client) {
+          if owned_client.window_label == webview_manager.current_window_label() {
+            store.remove(&client);
+          }
+        }
         Ok(().into())
       }
-      Self::HttpRequest { client, options } => {
+      Self::HttpRequest {
+        client,
+        options,
+        on_upload_progress,
+        stream,
+      } => {
+        #[cfg(http_request)]
+        return make_request(
+          webview_manager,
+          client,
+          *options,
+          on_upload_progress,
+          stream,
+        )
+        .await
+        .map(Into::into);
+        #[cfg(not(http_request))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > request".to_string(),
+        ))
+      }
+      Self::CreateStream => {
+        #[cfg(http_request)]
+        return create_stream(webview_manager).await.map(Into::into);
+        #[cfg(not(http_request))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > request".to_string(),
+        ))
+      }
+      Self::AckStreamChunk { id } => {
         #[cfg(http_request)]
-        return make_request(client, *options).await.map(Into::into);
+        return with_stream(webview_manager, id, |stream| stream.permits.add_permits(1))
+          .await
+          .map(Into::into);
         #[cfg(not(http_request))]
         Err(crate::Error::ApiNotAllowlisted(
           "http > request".to_string(),
         ))
       }
+      Self::CancelStream { id } => {
+        #[cfg(http_request)]
+        return cancel_stream(webview_manager, id).await.map(Into::into);
+        #[cfg(not(http_request))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > request".to_string(),
+        ))
+      }
+      Self::CreateDownload => {
+        #[cfg(http_download)]
+        return create_download(webview_manager).await.map(Into::into);
+        #[cfg(not(http_download))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > download".to_string(),
+        ))
+      }
+      Self::Download {
+        id,
+        client,
+        options,
+        on_progress,
+      } => {
+        #[cfg(http_download)]
+        return download(webview_manager, id, client, *options, on_progress)
+          .await
+          .map(Into::into);
+        #[cfg(not(http_download))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > download".to_string(),
+        ))
+      }
+      Self::PauseDownload { id } => {
+        #[cfg(http_download)]
+        return with_download(webview_manager, id, DownloadHandle::pause)
+          .await
+          .map(Into::into);
+        #[cfg(not(http_download))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > download".to_string(),
+        ))
+      }
+      Self::CancelDownload { id } => {
+        #[cfg(http_download)]
+        return with_download(webview_manager, id, DownloadHandle::cancel)
+          .await
+          .map(Into::into);
+        #[cfg(not(http_download))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > download".to_string(),
+        ))
+      }
     }
   }
 }
 
+/// Creates a HTTP client restricted to the configured scope and owned by the current window.
+///
+/// The clients are dropped when their window is closed or its page is unloaded.
+#[cfg(any(http_request, http_download))]
+async fn create_client<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  context: &Context,
+  mut options: ClientBuilder,
+) -> crate::Result<ClientId> {
+  let http_config = &context.config.tauri.allowlist.http;
+  if let Some(scope) = &http_config.scope {
+    options = options.scope(tauri_api::http::Scope::new(scope)?);
+  }
+  let client = options.build()?;
+
+  crate::event::on_window_destroyed("http-clients", |window_label| {
+    let window_label = window_label.to_string();
+    crate::async_runtime::spawn(async move {
+      clients()
+        .lock()
+        .await
+        .retain(|_, owned_client| owned_client.window_label != window_label);
+    });
+  });
+
+  let window_label = webview_manager.current_window_label().to_string();
+  let mut store = clients().lock().await;
+  let window_clients = store
+    .values()
+    .filter(|owned_client| owned_client.window_label == window_label)
+    .count();
+  if window_clients >= http_config.max_clients_per_window {
+    return Err(crate::Error::HttpClientLimitReached(
+      http_config.max_clients_per_window,
+    ));
+  }
+
+  let id = loop {
+    let id = rand::random::<ClientId>();
+    if !store.contains_key(&id) {
+      break id;
+    }
+  };
+  store.insert(
+    id,
+    OwnedClient {
+      window_label,
+      client,
+    },
+  );
+  Ok(id)
+}
+
 /// Makes an HTTP request and resolves the response to the webview
+///
+/// The form file parts require the `fs > readBinaryFile` allowlist.
+/// The upload progress is sent to the `on_upload_progress` callback of the current webview.
+/// If `stream` is set, the body is streamed to its callback and the response data is `null`.
 #[cfg(http_request)]
-pub async fn make_request(
+pub async fn make_request<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
   client_id: ClientId,
   options: HttpRequestBuilder,
+  on_upload_progress: Option<String>,
+  stream: Option<StreamOptions>,
+) -> crate::Result<ResponseData> {
+  // the form file parts are read from disk, so they're gated by the fs API
+  if let Some(Body::Form(form)) = &options.body {
+    if form.has_files() && cfg!(not(fs_read_binary_file)) {
+      return Err(crate::Error::ApiNotAllowlisted(
+        "fs > readBinaryFile".to_string(),
+      ));
+    }
+  }
+  let client = get_client(webview_manager, client_id).await?;
+  let stream = match stream {
+    Some(stream) => Some((start_stream(webview_manager, stream.id).await?, stream)),
+    None => None,
+  };
+  let response = match on_upload_progress {
+    Some(on_upload_progress) => {
+      let dispatcher = webview_manager.current_webview().await?;
+      client
+        .send_with_progress(options, move |progress| {
+          if let Ok(progress) = serde_json::to_value(progress) {
+            let _ = dispatcher.eval(&tauri_api::rpc::format_callback(
+              &on_upload_progress,
+              progress,
+            ));
+          }
+        })
+        .await?
+    }
+    None => client.send(options).await?,
+  };
+  match stream {
+    Some((permits, stream)) => stream_response(webview_manager, response, permits, stream).await,
+    None => Ok(response.read().await?),
+  }
+}
+
+/// Creates a response stream owned by the current window.
+///
+/// The streams are cancelled when their window is closed or its page is unloaded.
+#[cfg(http_request)]
+async fn create_stream<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+) -> crate::Result<StreamId> {
+  crate::event::on_window_destroyed("http-streams", |window_label| {
+    let window_label = window_label.to_string();
+    crate::async_runtime::spawn(async move {
+      streams().lock().await.retain(|_, stream| {
+        if stream.window_label == window_label {
+          stream.abort();
+          false
+        } else {
+          true
+        }
+      });
+    });
+  });
+  let mut store = streams().lock().await;
+  let id = loop {
+    let id = rand::random::<StreamId>();
+    if !store.contains_key(&id) {
+      break id;
+    }
+  };
+  store.insert(
+    id,
+    ResponseStream {
+      window_label: webview_manager.current_window_label().to_string(),
+      permits: Arc::new(Semaphore::new(STREAM_CHUNK_WINDOW)),
+      started: false,
+      abort_handle: None,
+    },
+  );
+  Ok(id)
+}
+
+/// Marks a stream created by the current window as started, returning its chunk permits.
+#[cfg(http_request)]
+async fn start_stream<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: StreamId,
+) -> crate::Result<Arc<Semaphore>> {
+  match streams().lock().await.get_mut(&id).filter(|stream| {
+    stream.window_label == webview_manager.current_window_label() && !stream.started
+  }) {
+    Some(stream) => {
+      stream.started = true;
+      Ok(stream.permits.clone())
+    }
+    None => Err(crate::Error::StreamNotFound),
+  }
+}
+
+/// Cancels a stream of the current window, aborting the request.
+#[cfg(http_request)]
+async fn cancel_stream<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: StreamId,
+) -> crate::Result<()> {
+  let mut store = streams().lock().await;
+  if store.get(&id).map_or(false, |stream| {
+    stream.window_label == webview_manager.current_window_label()
+  }) {
+    if let Some(stream) = store.remove(&id) {
+      stream.abort();
+    }
+  }
+  Ok(())
+}
+
+/// Streams the response body to the `on_event` callback of the current webview.
+///
+/// A chunk is only read from the network once the webview has acknowledged enough of the previous ones.
+#[cfg(http_request)]
+async fn stream_response<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  response: tauri_api::http::Response,
+  stream_permits: Arc<Semaphore>,
+  options: StreamOptions,
 ) -> crate::Result<ResponseData> {
-  let client = clients()
+  let dispatcher = webview_manager.current_webview().await?;
+  let response_data = response.head()?;
+  let StreamOptions { id, on_event } = options;
+  let emit = move |event: StreamEvent| {
+    if let Ok(event) = serde_json::to_value(event) {
+      let _ = dispatcher.eval(&tauri_api::rpc::format_callback(&on_event, event));
+    }
+  };
+
+  let emit_body = emit.clone();
+  let (task, abort_handle) = futures::future::abortable(async move {
+    let mut body = Box::pin(response.bytes_stream());
+    loop {
+      match stream_permits.acquire().await {
+        Ok(permit) => permit.forget(),
+        Err(_) => break,
+      }
+      match body.next().await {
+        Some(Ok(chunk)) => emit_body(StreamEvent::Chunk(base64::encode(&chunk))),
+        Some(Err(e)) => {
+          emit_body(StreamEvent::Error(e.to_string()));
+          break;
+        }
+        None => {
+          emit_body(StreamEvent::End);
+          break;
+        }
+      }
+    }
+  });
+
+  // the stream was cancelled while waiting for the response
+  match streams().lock().await.get_mut(&id) {
+    Some(stream) => stream.abort_handle = Some(abort_handle),
+    None => abort_handle.abort(),
+  }
+  crate::async_runtime::spawn(async move {
+    // the webview is notified when the stream is aborted, so it doesn't wait for the end of the body
+    if task.await.is_err() {
+      emit(StreamEvent::Cancelled);
+    }
+    streams().lock().await.remove(&id);
+  });
+
+  Ok(response_data)
+}
+
+/// Runs the operation on a stream of the current window if it's still running.
+#[cfg(http_request)]
+async fn with_stream<A: crate::ApplicationExt + 'static, F: FnOnce(&ResponseStream)>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: StreamId,
+  operation: F,
+) -> crate::Result<()> {
+  if let Some(stream) = streams()
+    .lock()
+    .await
+    .get(&id)
+    .filter(|stream| stream.window_label == webview_manager.current_window_label())
+  {
+    operation(stream);
+  }
+  Ok(())
+}
+
+/// Creates a download owned by the current window.
+///
+/// The downloads are paused when their window is closed or its page is unloaded.
+#[cfg(http_download)]
+async fn create_download<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+) -> crate::Result<DownloadId> {
+  crate::event::on_window_destroyed("http-downloads", |window_label| {
+    let window_label = window_label.to_string();
+    crate::async_runtime::spawn(async move {
+      downloads().lock().await.retain(|_, download| {
+        if download.window_label == window_label {
+          download.handle.pause();
+          false
+        } else {
+          true
+        }
+      });
+    });
+  });
+  let mut store = downloads().lock().await;
+  let id = loop {
+    let id = rand::random::<DownloadId>();
+    if !store.contains_key(&id) {
+      break id;
+    }
+  };
+  store.insert(
+    id,
+    OwnedDownload {
+      window_label: webview_manager.current_window_label().to_string(),
+      handle: DownloadHandle::new(),
+      started: false,
+    },
+  );
+  Ok(id)
+}
+
+/// Downloads a file, sending the progress to the `on_progress` callback of the current webview.
+#[cfg(http_download)]
+pub async fn download<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: DownloadId,
+  client_id: ClientId,
+  options: DownloadOptions,
+  on_progress: Option<String>,
+) -> crate::Result<DownloadStatus> {
+  let client = get_client(webview_manager, client_id).await?;
+  let dispatcher = webview_manager.current_webview().await?;
+
+  let mut request = options.request;
+  request.path = tauri_api::path::resolve_path(&request.path, options.dir)?;
+
+  let handle = match downloads().lock().await.get_mut(&id).filter(|download| {
+    download.window_label == webview_manager.current_window_label() && !download.started
+  }) {
+    Some(download) => {
+      download.started = true;
+      download.handle.clone()
+    }
+    None => return Err(crate::Error::DownloadNotFound),
+  };
+  let status = client
+    .download(request, &handle, |progress| {
+      if let Some(on_progress) = &on_progress {
+        if let Ok(progress) = serde_json::to_value(progress) {
+          let _ = dispatcher.eval(&tauri_api::rpc::format_callback(on_progress, progress));
+        }
+      }
+    })
+    .await;
+  downloads().lock().await.remove(&id);
+
+  Ok(status?)
+}
+
+/// Runs the operation on the download handle if the download was created by the current window and is still running.
+#[cfg(http_download)]
+async fn with_download<A: crate::ApplicationExt + 'static, F: FnOnce(&DownloadHandle)>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: DownloadId,
+  operation: F,
+) -> crate::Result<()> {
+  if let Some(download) = downloads()
+    .lock()
+    .await
+    .get(&id)
+    .filter(|download| download.window_label == webview_manager.current_window_label())
+  {
+    operation(&download.handle);
+  }
+  Ok(())
+}
+
+/// Gets a client created by the current window.
+#[cfg(any(http_request, http_download))]
+async fn get_client<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  client_id: ClientId,
+) -> crate::Result<Client> {
+  clients()
     .lock()
     .await
     .get(&client_id)
-    .ok_or(crate::Error::HttpClientNotInitialized)?
-    .clone();
-  let response = client.send(options).await?;
-  Ok(response.read().await?)
+    .filter(|owned_client| owned_client.window_label == webview_manager.current_window_label())
+    .map(|owned_client| owned_client.client.clone())
+    .ok_or(crate::Error::HttpClientNotInitialized)
 }
@@ -1,17 +1,43 @@
-use crate::app::InvokeResponse;
+use crate::app::{ApplicationExt, InvokeResponse};
 use serde::Deserialize;
+use serde_json::Value as JsonValue;
 
 /// The API descriptor.
 #[derive(Deserialize)]
 #[serde(tag = "cmd", rename_all = "camelCase")]
 pub enum Cmd {
-  ValidateSalt { salt: String },
+  ValidateSalt {
+    salt: String,
+  },
+  /// The result of a script evaluated with `eval_with_result`.
+  EvalResult {
+    id: String,
+    result: Option<JsonValue>,
+    error: Option<String>,
+  },
+  /// The window page is being unloaded, releasing the resources it owns.
+  Unload,
 }
 
 impl Cmd {
-  pub async fn run(self) -> crate::Result<InvokeResponse> {
+  pub async fn run<A: ApplicationExt + 'static>(
+    self,
+    webview_manager: &crate::WebviewManager<A>,
+  ) -> crate::Result<InvokeResponse> {
     match self {
       Self::ValidateSalt { salt } => validate_salt(salt),
+      Self::EvalResult { id, result, error } => {
+        let result = match error {
+          Some(error) => Err(error),
+          None => Ok(result.unwrap_or(JsonValue::Null)),
+        };
+        crate::app::eval::resolve(&id, webview_manager.current_window_label(), result);
+        Ok(().into())
+      }
+      Self::Unload => {
+        crate::event::window_destroyed(webview_manager.current_window_label());
+        Ok(().into())
+      }
     }
   }
 }
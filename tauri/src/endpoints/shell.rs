@@ -1,39 +1,239 @@
 use crate::app::InvokeResponse;
+#[cfg(shell_execute)]
+use crate::async_runtime::Mutex;
+
+#[cfg(shell_execute)]
+use once_cell::sync::{Lazy, OnceCell};
+#[cfg(shell_execute)]
+use regex::Regex;
 use serde::Deserialize;
+use tauri_api::shell::Program;
+#[cfg(shell_execute)]
+use tauri_api::{
+  command::{CommandBuilder, CommandChild},
+  config::{ShellAllowedArg, ShellAllowedArgs, ShellAllowedCommand},
+};
+
+#[cfg(shell_execute)]
+use std::sync::Arc;
+use std::{collections::HashMap, path::PathBuf};
+
+type ChildId = u32;
+#[cfg(shell_execute)]
+type ChildStore = Arc<Mutex<HashMap<ChildId, OwnedChild>>>;
+
+/// A child process and the label of the window that spawned it.
+#[cfg(shell_execute)]
+struct OwnedChild {
+  window_label: String,
+  child: CommandChild,
+}
+
+#[cfg(shell_execute)]
+fn children() -> &'static ChildStore {
+  static STORE: Lazy<ChildStore> = Lazy::new(Default::default);
+  &STORE
+}
+
+/// A command of the shell scope with its argument validators compiled.
+#[cfg(shell_execute)]
+struct ScopeCommand {
+  command: ShellAllowedCommand,
+  /// The validator of each argument, `None` for the fixed ones.
+  validators: Vec<Option<Regex>>,
+}
+
+/// The shell scope, built once from the `tauri > allowlist > shell > scope` config.
+#[cfg(shell_execute)]
+struct ShellScope(Vec<ScopeCommand>);
+
+#[cfg(shell_execute)]
+impl ShellScope {
+  /// Builds the scope, compiling the argument validators.
+  fn new(scope: &[ShellAllowedCommand]) -> crate::Result<Self> {
+    let mut commands = Vec::new();
+    for command in scope {
+      let mut validators = Vec::new();
+      if let ShellAllowedArgs::List(args) = &command.args {
+        for arg in args {
+          validators.push(match arg {
+            ShellAllowedArg::Fixed(_) => None,
+            ShellAllowedArg::Var { validator } => {
+              Some(Regex::new(&format!("^(?:{})$", validator)).map_err(|e| {
+                crate::Error::CommandNotAllowed(format!("invalid validator: {}", e))
+              })?)
+            }
+          });
+        }
+      }
+      commands.push(ScopeCommand {
+        command: command.clone(),
+        validators,
+      });
+    }
+    Ok(Self(commands))
+  }
+
+  /// Gets the scope command with the given name, checking that it allows the arguments and the options.
+  fn command(
+    &self,
+    name: &str,
+    args: &[String],
+    options: &CommandOptions,
+  ) -> crate::Result<&ShellAllowedCommand> {
+    let scope_command = self
+      .0
+      .iter()
+      .find(|scope_command| scope_command.command.name == name)
+      .ok_or_else(|| crate::Error::CommandNotAllowed(name.to_string()))?;
+    let command = &scope_command.command;
+    let not_allowed = || crate::Error::CommandNotAllowed(format!("{} {}", name, args.join(" ")));
+    match &command.args {
+      ShellAllowedArgs::Flag(true) => {}
+      ShellAllowedArgs::Flag(false) if args.is_empty() => {}
+      ShellAllowedArgs::Flag(false) => return Err(not_allowed()),
+      ShellAllowedArgs::List(allowed) => {
+        if allowed.len() != args.len() {
+          return Err(not_allowed());
+        }
+        for ((allowed, validator), arg) in allowed.iter().zip(&scope_command.validators).zip(args) {
+          let matches = match allowed {
+            ShellAllowedArg::Fixed(fixed) => fixed == arg,
+            ShellAllowedArg::Var { .. } => validator
+              .as_ref()
+              .map_or(false, |validator| validator.is_match(arg)),
+          };
+          if !matches {
+            return Err(not_allowed());
+          }
+        }
+      }
+    }
+    if options.cwd.is_some() && !command.cwd {
+      return Err(crate::Error::CommandNotAllowed(format!(
+        "{} with a working directory",
+        name
+      )));
+    }
+    if let Some(env) = &options.env {
+      if let Some(key) = env.keys().find(|key| !command.env.contains(key)) {
+        return Err(crate::Error::CommandNotAllowed(format!(
+          "{} with the environment variable {}",
+          name, key
+        )));
+      }
+    }
+    Ok(command)
+  }
+}
+
+/// Gets the shell scope of the app.
+#[cfg(shell_execute)]
+fn shell_scope(context: &crate::app::Context) -> crate::Result<&'static ShellScope> {
+  static SCOPE: OnceCell<ShellScope> = OnceCell::new();
+  SCOPE.get_or_try_init(|| ShellScope::new(&context.config.tauri.allowlist.shell.scope))
+}
+
+/// The options for the execute API.
+/// They're only allowed if the scope command allows them.
+#[derive(Default, Deserialize)]
+pub struct CommandOptions {
+  /// The process working directory.
+  cwd: Option<PathBuf>,
+  /// The environment variables added to the ones of the app.
+  env: Option<HashMap<String, String>>,
+}
+
+/// The data written to a child process stdin.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum Buffer {
+  /// A text, written as UTF-8.
+  Text(String),
+  /// Raw bytes.
+  Raw(Vec<u8>),
+}
 
 /// The API descriptor.
 #[derive(Deserialize)]
 #[serde(tag = "cmd", rename_all = "camelCase")]
 pub enum Cmd {
-  /// The execute script API.
-  Execute { command: String, args: Vec<String> },
-  /// The open URL in browser API
-  Open { uri: String },
+  /// Spawns a command of the shell scope, referred to by its name, resolving to the child process ID.
+  /// The output lines and the exit status are sent to the `on_event` callback.
+  #[serde(rename_all = "camelCase")]
+  Execute {
+    command: String,
+    args: Vec<String>,
+    #[serde(default)]
+    options: CommandOptions,
+    on_event: String,
+  },
+  /// Writes to the stdin of a child process.
+  StdinWrite { id: ChildId, buffer: Buffer },
+  /// Kills a child process.
+  KillChild { id: ChildId },
+  /// Opens a path or URL allowed by the `openSchemes` config with the default or the given program.
+  Open {
+    #[serde(alias = "uri")]
+    path: String,
+    with: Option<Program>,
+  },
+  /// Shows a file or directory in the file manager.
+  Reveal { path: PathBuf },
 }
 
 impl Cmd {
-  pub async fn run(self) -> crate::Result<InvokeResponse> {
+  pub async fn run<A: crate::ApplicationExt + 'static>(
+    self,
+    webview_manager: &crate::WebviewManager<A>,
+    context: &crate::app::Context,
+  ) -> crate::Result<InvokeResponse> {
     match self {
       Self::Execute {
-        command: _,
-        args: _,
+        command,
+        args,
+        options,
+        on_event,
       } => {
         #[cfg(shell_execute)]
-        {
-          //TODO
-          Ok(().into())
-        }
+        return execute(webview_manager, context, command, args, options, on_event)
+          .await
+          .map(Into::into);
+        #[cfg(not(shell_execute))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "shell > execute".to_string(),
+        ))
+      }
+      Self::StdinWrite { id, buffer } => {
+        #[cfg(shell_execute)]
+        return write_stdin(webview_manager, id, buffer)
+          .await
+          .map(Into::into);
+        #[cfg(not(shell_execute))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "shell > execute".to_string(),
+        ))
+      }
+      Self::KillChild { id } => {
+        #[cfg(shell_execute)]
+        return get_child(webview_manager, id)
+          .await
+          .map(|child| child.kill())
+          .map(Into::into);
         #[cfg(not(shell_execute))]
         Err(crate::Error::ApiNotAllowlisted(
           "shell > execute".to_string(),
         ))
       }
-      Self::Open { uri } => {
+      Self::Open { path, with } => {
         #[cfg(shell_open)]
-        {
-          open_browser(uri);
-          Ok(().into())
-        }
+        return open(context, path, with).await.map(Into::into);
+        #[cfg(not(shell_open))]
+        Err(crate::Error::ApiNotAllowlisted("shell > open".to_string()))
+      }
+      Self::Reveal { path } => {
+        #[cfg(shell_open)]
+        return reveal(context, path).await.map(Into::into);
         #[cfg(not(shell_open))]
         Err(crate::Error::ApiNotAllowlisted("shell > open".to_string()))
       }
@@ -41,24 +241,175 @@ impl Cmd {
   }
 }
 
+/// Spawns a command of the shell scope, owned by the current window,
+/// sending its events to the `on_event` callback of the current webview.
+///
+/// The children are killed when their window is closed or its page is unloaded.
+#[cfg(shell_execute)]
+async fn execute<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  context: &crate::app::Context,
+  name: String,
+  args: Vec<String>,
+  options: CommandOptions,
+  on_event: String,
+) -> crate::Result<ChildId> {
+  let command = shell_scope(context)?.command(&name, &args, &options)?;
+  let program = if command.sidecar {
+    tauri_api::sidecar::sidecar_path(&command.cmd)?
+      .to_string_lossy()
+      .into_owned()
+  } else {
+    command.cmd.clone()
+  };
+  let mut builder = CommandBuilder::new(program).args(args);
+  if let Some(cwd) = options.cwd {
+    builder = builder.current_dir(cwd);
+  }
+  if let Some(env) = options.env {
+    builder = builder.envs(env);
+  }
+  let (mut events, child) = builder.spawn()?;
+  let id = child.pid();
+
+  let dispatcher = webview_manager.current_webview().await?;
+  let window_label = webview_manager.current_window_label().to_string();
+  crate::event::on_window_destroyed("shell-children", |window_label| {
+    let window_label = window_label.to_string();
+    crate::async_runtime::spawn(async move {
+      for owned_child in children().lock().await.values() {
+        if owned_child.window_label == window_label {
+          owned_child.child.kill();
+        }
+      }
+    });
+  });
+  children().lock().await.insert(
+    id,
+    OwnedChild {
+      window_label,
+      child,
+    },
+  );
+
+  crate::async_runtime::spawn(async move {
+    while let Some(event) = events.recv().await {
+      // tags the event with the child ID
+      if let Ok(serde_json::Value::Object(mut event)) = serde_json::to_value(event) {
+        event.insert("id".to_string(), id.into());
+        let _ = dispatcher.eval(&tauri_api::rpc::format_callback(
+          &on_event,
+          serde_json::Value::Object(event),
+        ));
+      }
+    }
+    children().lock().await.remove(&id);
+  });
+
+  Ok(id)
+}
+
+/// Writes to the stdin of a child process of the current window.
+#[cfg(shell_execute)]
+async fn write_stdin<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: ChildId,
+  buffer: Buffer,
+) -> crate::Result<()> {
+  let child = get_child(webview_manager, id).await?;
+  match buffer {
+    Buffer::Text(text) => child.write(text.as_bytes()).await?,
+    Buffer::Raw(data) => child.write(&data).await?,
+  }
+  Ok(())
+}
+
+/// Gets a child process spawned by the current window.
+#[cfg(shell_execute)]
+async fn get_child<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: ChildId,
+) -> crate::Result<CommandChild> {
+  children()
+    .lock()
+    .await
+    .get(&id)
+    .filter(|owned_child| owned_child.window_label == webview_manager.current_window_label())
+    .map(|owned_child| owned_child.child.clone())
+    .ok_or(crate::Error::ChildProcessNotFound)
+}
+
+/// Opens a path or URL if its scheme is allowed by the `openSchemes` config.
 #[cfg(shell_open)]
-pub fn open_browser(uri: String) {
-  #[cfg(test)]
-  assert!(uri.contains("http://"));
+async fn open(
+  context: &crate::app::Context,
+  path: String,
+  with: Option<Program>,
+) -> crate::Result<()> {
+  tauri_api::shell::check_scheme(&path, &context.config.tauri.allowlist.shell.open_schemes)?;
+  tauri_api::shell::open(path, with).await.map_err(Into::into)
+}
 
-  #[cfg(not(test))]
-  webbrowser::open(&uri).expect("Failed to open webbrowser with uri");
+/// Shows a path in the file manager if the `file` scheme is allowed by the `openSchemes` config,
+/// like the paths opened by the open API.
+#[cfg(shell_open)]
+async fn reveal(context: &crate::app::Context, path: PathBuf) -> crate::Result<()> {
+  tauri_api::shell::check_scheme("file:", &context.config.tauri.allowlist.shell.open_schemes)?;
+  tauri_api::shell::reveal(path).await.map_err(Into::into)
 }
 
 #[cfg(test)]
 mod test {
   use proptest::prelude::*;
-  // Test the open func to see if proper uris can be opened by the browser.
+  // Test the open scope to see if proper uris can be opened by the browser.
   proptest! {
     #[cfg(shell_open)]
     #[test]
-    fn check_open(uri in r"(http://)([\\w\\d\\.]+([\\w]{2,6})?)") {
-      super::open_browser(uri);
+    fn check_open(uri in r"http://[a-z][a-z0-9]*(\.[a-z]{2,6})?") {
+      let schemes = vec!["http".to_string()];
+      prop_assert!(tauri_api::shell::check_scheme(&uri, &schemes).is_ok());
+      prop_assert!(tauri_api::shell::check_scheme(&uri, &[]).is_err());
     }
   }
+
+  #[cfg(shell_execute)]
+  #[test]
+  fn check_scope_command() {
+    let scope: Vec<tauri_api::config::ShellAllowedCommand> =
+      serde_json::from_value(serde_json::json!([
+        { "name": "git-status", "cmd": "git", "args": ["status", { "validator": "--\\w+" }] },
+        { "name": "ls", "cmd": "ls", "args": true, "cwd": true },
+        { "name": "pwd", "cmd": "pwd", "env": ["LANG"] }
+      ]))
+      .unwrap();
+    let scope = super::ShellScope::new(&scope).unwrap();
+    let allowed_with = |name: &str, args: &[&str], options: super::CommandOptions| {
+      let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+      scope.command(name, &args, &options).is_ok()
+    };
+    let allowed = |name: &str, args: &[&str]| allowed_with(name, args, Default::default());
+
+    assert!(allowed("git-status", &["status", "--short"]));
+    assert!(!allowed("git-status", &["status", "--short; rm"]));
+    assert!(!allowed("git-status", &["status"]));
+    assert!(!allowed("git-status", &["push", "--force"]));
+    assert!(allowed("ls", &["-la", "/"]));
+    assert!(allowed("pwd", &[]));
+    assert!(!allowed("pwd", &["-P"]));
+    assert!(!allowed("git", &["status"]));
+
+    let cwd = || super::CommandOptions {
+      cwd: Some("/".into()),
+      env: None,
+    };
+    let env = |key: &str| super::CommandOptions {
+      cwd: None,
+      env: Some(std::iter::once((key.to_string(), "C".to_string())).collect()),
+    };
+    assert!(allowed_with("ls", &[], cwd()));
+    assert!(!allowed_with("pwd", &[], cwd()));
+    assert!(allowed_with("pwd", &[], env("LANG")));
+    assert!(!allowed_with("pwd", &[], env("LD_PRELOAD")));
+    assert!(!allowed_with("ls", &[], env("LANG")));
+  }
 }
@@ -0,0 +1,41 @@
+use crate::app::InvokeResponse;
+#[cfg(store_all)]
+use crate::store::Store;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+#[cfg_attr(not(store_all), allow(dead_code))]
+pub enum Cmd {
+  /// Gets a value from the store.
+  Get { store: String, key: String },
+  /// Sets a value on the store.
+  Set {
+    store: String,
+    key: String,
+    value: JsonValue,
+  },
+  /// Deletes a key from the store.
+  Delete { store: String, key: String },
+  /// Gets all store entries.
+  Entries { store: String },
+  /// Removes all entries from the store.
+  Clear { store: String },
+}
+
+impl Cmd {
+  pub async fn run(self) -> crate::Result<InvokeResponse> {
+    #[cfg(not(store_all))]
+    return Err(crate::Error::ApiNotAllowlisted("store > all".to_string()));
+    #[cfg(store_all)]
+    match self {
+      Self::Get { store, key } => Ok(Store::new(store)?.get(key)?.into()),
+      Self::Set { store, key, value } => Ok(Store::new(store)?.set(key, value)?.into()),
+      Self::Delete { store, key } => Ok(Store::new(store)?.delete(key)?.into()),
+      Self::Entries { store } => Ok(Store::new(store)?.entries()?.into()),
+      Self::Clear { store } => Ok(Store::new(store)?.clear()?.into()),
+    }
+  }
+}
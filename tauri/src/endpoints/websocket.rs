@@ -0,0 +1,259 @@
+use crate::app::{Context, InvokeResponse};
+#[cfg(http_websocket)]
+use crate::async_runtime::Mutex;
+
+#[cfg(http_websocket)]
+use futures::future::AbortHandle;
+#[cfg(http_websocket)]
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+#[cfg(http_websocket)]
+use serde::Serialize;
+#[cfg(http_websocket)]
+use tauri_api::websocket::{self, ConnectRequestBuilder, Event, WebSocketSender};
+
+use std::collections::HashMap;
+#[cfg(http_websocket)]
+use std::sync::Arc;
+
+type ConnectionId = u32;
+#[cfg(http_websocket)]
+type ConnectionStore = Arc<Mutex<HashMap<ConnectionId, Connection>>>;
+
+/// A WebSocket connection and the label of the window that opened it.
+#[cfg(http_websocket)]
+struct Connection {
+  window_label: String,
+  sender: Arc<Mutex<WebSocketSender>>,
+  /// Stops reading the connection events.
+  abort_handle: AbortHandle,
+}
+
+#[cfg(http_websocket)]
+fn connections() -> &'static ConnectionStore {
+  static STORE: Lazy<ConnectionStore> = Lazy::new(Default::default);
+  &STORE
+}
+
+/// The options for the connect API.
+#[derive(Deserialize)]
+#[cfg_attr(not(http_websocket), allow(dead_code))]
+pub struct ConnectOptions {
+  /// The WebSocket URL.
+  url: String,
+  /// The handshake request headers.
+  headers: Option<HashMap<String, String>>,
+}
+
+/// A WebSocket data message.
+#[derive(Deserialize)]
+#[cfg_attr(not(http_websocket), allow(dead_code))]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum Message {
+  /// A text message.
+  Text(String),
+  /// A binary message.
+  Binary(Vec<u8>),
+}
+
+/// The close frame of a WebSocket connection.
+#[derive(Deserialize)]
+#[cfg_attr(not(http_websocket), allow(dead_code))]
+pub struct CloseFrame {
+  /// The close status code.
+  code: u16,
+  /// The close reason.
+  #[serde(default)]
+  reason: String,
+}
+
+/// The events sent to the connection callback.
+#[cfg(http_websocket)]
+#[derive(Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+enum ConnectionEvent {
+  /// A data message was received.
+  Message(websocket::Message),
+  /// The connection was closed.
+  Close(Option<websocket::CloseFrame>),
+  /// The connection failed; no more events are sent.
+  Error(String),
+}
+
+/// The API descriptor.
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "camelCase")]
+pub enum Cmd {
+  /// Connects to a WebSocket server, resolving to the connection ID once the handshake finishes.
+  #[serde(rename_all = "camelCase")]
+  Connect {
+    options: ConnectOptions,
+    on_event: String,
+  },
+  /// Sends a message on a connection.
+  Send { id: ConnectionId, message: Message },
+  /// Starts the close handshake of a connection.
+  Close {
+    id: ConnectionId,
+    frame: Option<CloseFrame>,
+  },
+}
+
+impl Cmd {
+  #[allow(unused_variables)]
+  pub async fn run<A: crate::ApplicationExt + 'static>(
+    self,
+    webview_manager: &crate::WebviewManager<A>,
+    context: &Context,
+  ) -> crate::Result<InvokeResponse> {
+    match self {
+      Self::Connect { options, on_event } => {
+        #[cfg(http_websocket)]
+        return connect(webview_manager, context, options, on_event)
+          .await
+          .map(Into::into);
+        #[cfg(not(http_websocket))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > websocket".to_string(),
+        ))
+      }
+      Self::Send { id, message } => {
+        #[cfg(http_websocket)]
+        return send(webview_manager, id, message).await.map(Into::into);
+        #[cfg(not(http_websocket))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > websocket".to_string(),
+        ))
+      }
+      Self::Close { id, frame } => {
+        #[cfg(http_websocket)]
+        return close(webview_manager, id, frame).await.map(Into::into);
+        #[cfg(not(http_websocket))]
+        Err(crate::Error::ApiNotAllowlisted(
+          "http > websocket".to_string(),
+        ))
+      }
+    }
+  }
+}
+
+/// Connects to a WebSocket server allowed by the HTTP scope,
+/// sending the connection events to the `on_event` callback of the current webview.
+///
+/// The connections are closed when their window is closed or its page is unloaded.
+#[cfg(http_websocket)]
+async fn connect<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  context: &Context,
+  options: ConnectOptions,
+  on_event: String,
+) -> crate::Result<ConnectionId> {
+  let mut request = ConnectRequestBuilder::new(options.url);
+  if let Some(headers) = options.headers {
+    request = request.headers(headers);
+  }
+  if let Some(scope) = &context.config.tauri.allowlist.http.scope {
+    request = request.scope(tauri_api::http::Scope::new(scope)?);
+  }
+  let (sender, mut receiver) = websocket::connect(request).await?;
+
+  let dispatcher = webview_manager.current_webview().await?;
+  let window_label = webview_manager.current_window_label().to_string();
+  let (task, abort_handle) = futures::future::abortable(async move {
+    while let Some(event) = receiver.next().await {
+      let (event, error) = match event {
+        Ok(Event::Message(message)) => (ConnectionEvent::Message(message), false),
+        Ok(Event::Close(frame)) => (ConnectionEvent::Close(frame), false),
+        Err(e) => (ConnectionEvent::Error(e.to_string()), true),
+      };
+      if let Ok(event) = serde_json::to_value(event) {
+        let _ = dispatcher.eval(&tauri_api::rpc::format_callback(&on_event, event));
+      }
+      if error {
+        break;
+      }
+    }
+  });
+
+  crate::event::on_window_destroyed("websocket-connections", |window_label| {
+    let window_label = window_label.to_string();
+    crate::async_runtime::spawn(async move {
+      connections().lock().await.retain(|_, connection| {
+        if connection.window_label == window_label {
+          connection.abort_handle.abort();
+          false
+        } else {
+          true
+        }
+      });
+    });
+  });
+  let mut store = connections().lock().await;
+  let id = loop {
+    let id = rand::random::<ConnectionId>();
+    if !store.contains_key(&id) {
+      break id;
+    }
+  };
+  store.insert(
+    id,
+    Connection {
+      window_label,
+      sender: Arc::new(Mutex::new(sender)),
+      abort_handle,
+    },
+  );
+  crate::async_runtime::spawn(async move {
+    let _ = task.await;
+    connections().lock().await.remove(&id);
+  });
+
+  Ok(id)
+}
+
+/// Sends a message on a connection of the current window.
+#[cfg(http_websocket)]
+async fn send<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: ConnectionId,
+  message: Message,
+) -> crate::Result<()> {
+  let message = match message {
+    Message::Text(text) => websocket::Message::Text(text),
+    Message::Binary(data) => websocket::Message::Binary(data),
+  };
+  let sender = get_sender(webview_manager, id).await?;
+  let mut sender = sender.lock().await;
+  sender.send(message).await.map_err(Into::into)
+}
+
+/// Starts the close handshake of a connection of the current window.
+#[cfg(http_websocket)]
+async fn close<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: ConnectionId,
+  frame: Option<CloseFrame>,
+) -> crate::Result<()> {
+  let frame = frame.map(|frame| websocket::CloseFrame {
+    code: frame.code,
+    reason: frame.reason,
+  });
+  let sender = get_sender(webview_manager, id).await?;
+  let mut sender = sender.lock().await;
+  sender.close(frame).await.map_err(Into::into)
+}
+
+/// Gets the sending half of a connection opened by the current window.
+#[cfg(http_websocket)]
+async fn get_sender<A: crate::ApplicationExt + 'static>(
+  webview_manager: &crate::WebviewManager<A>,
+  id: ConnectionId,
+) -> crate::Result<Arc<Mutex<WebSocketSender>>> {
+  connections()
+    .lock()
+    .await
+    .get(&id)
+    .filter(|connection| connection.window_label == webview_manager.current_window_label())
+    .map(|connection| connection.sender.clone())
+    .ok_or(crate::Error::WebSocketNotConnected)
+}
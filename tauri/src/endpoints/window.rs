@@ -79,6 +79,12 @@ pub enum Cmd {
   SetIcon {
     icon: IconDto,
   },
+  Navigate {
+    url: String,
+  },
+  Reload,
+  GoBack,
+  GoForward,
 }
 
 #[cfg(window_create)]
@@ -88,9 +94,11 @@ struct WindowCreatedEvent {
 }
 
 impl Cmd {
+  #[allow(unused_variables)]
   pub async fn run<A: ApplicationExt + 'static>(
     self,
     webview_manager: &crate::WebviewManager<A>,
+    context: &crate::app::Context,
   ) -> crate::Result<InvokeResponse> {
     if cfg!(not(window_all)) {
       Err(crate::Error::ApiNotAllowlisted("window > all".to_string()))
@@ -147,8 +155,53 @@ impl Cmd {
         Self::SetPosition { x, y } => current_webview.set_position(x, y)?,
         Self::SetFullscreen { fullscreen } => current_webview.set_fullscreen(fullscreen)?,
         Self::SetIcon { icon } => current_webview.set_icon(icon.into())?,
+        Self::Navigate { url } => {
+          #[cfg(not(window_navigate))]
+          return Err(crate::Error::ApiNotAllowlisted(
+            "window > navigate".to_string(),
+          ));
+          #[cfg(window_navigate)]
+          {
+            check_navigation_scope(context, &url)?;
+            current_webview.navigate(&url)?
+          }
+        }
+        Self::Reload => {
+          #[cfg(not(window_navigate))]
+          return Err(crate::Error::ApiNotAllowlisted(
+            "window > navigate".to_string(),
+          ));
+          #[cfg(window_navigate)]
+          current_webview.reload()?
+        }
+        Self::GoBack => {
+          #[cfg(not(window_navigate))]
+          return Err(crate::Error::ApiNotAllowlisted(
+            "window > navigate".to_string(),
+          ));
+          #[cfg(window_navigate)]
+          current_webview.go_back()?
+        }
+        Self::GoForward => {
+          #[cfg(not(window_navigate))]
+          return Err(crate::Error::ApiNotAllowlisted(
+            "window > navigate".to_string(),
+          ));
+          #[cfg(window_navigate)]
+          current_webview.go_forward()?
+        }
       }
       Ok(().into())
     }
   }
 }
+
+/// Checks if the URL is allowed by the window navigation scope.
+#[cfg(window_navigate)]
+fn check_navigation_scope(context: &crate::app::Context, url: &str) -> crate::Result<()> {
+  let scope = tauri_api::http::Scope::new(&context.config.tauri.allowlist.window.navigation_scope)?;
+  match url.parse() {
+    Ok(parsed) if scope.is_allowed(&parsed) => Ok(()),
+    _ => Err(crate::Error::NavigationNotAllowed(url.to_string())),
+  }
+}
@@ -37,6 +37,48 @@ pub enum Error {
   /// Client with specified ID not found.
   #[error("http client dropped or not initialized")]
   HttpClientNotInitialized,
+  /// The window reached the maximum number of live HTTP clients.
+  #[error("the window reached the maximum number of HTTP clients ({0})")]
+  HttpClientLimitReached(usize),
+  /// Download with specified ID not found.
+  #[error("download finished, already started or not created")]
+  DownloadNotFound,
+  /// Response stream with specified ID not found.
+  #[error("stream finished, already started or not created")]
+  StreamNotFound,
+  /// WebSocket connection with specified ID not found.
+  #[error("websocket connection closed or not initialized")]
+  WebSocketNotConnected,
+  /// The URL isn't allowed by the window navigation scope or navigation handler.
+  #[error("navigation not allowed: {0}")]
+  NavigationNotAllowed(String),
+  /// The command or its arguments aren't allowed by the shell scope.
+  #[error("command not allowed by the shell scope: {0}")]
+  CommandNotAllowed(String),
+  /// Child process with specified ID not found.
+  #[error("child process terminated or not spawned")]
+  ChildProcessNotFound,
+  /// File system watcher with specified ID not found.
+  #[error("file system watcher stopped or not started")]
+  WatcherNotFound,
+  /// File with specified ID not open.
+  #[error("file closed or not open")]
+  FileNotOpen,
+  /// The async runtime was already initialized.
+  #[error("the async runtime was already initialized")]
+  AsyncRuntimeAlreadyInitialized,
+  /// A script evaluated with `eval_with_result` threw an error.
+  #[error("script evaluation failed: {0}")]
+  EvalScript(String),
+  /// A crash report file couldn't be read.
+  #[error("invalid crash report {0}: {1}")]
+  InvalidCrashReport(std::path::PathBuf, String),
+  /// The crash reporter logger couldn't be set.
+  #[error("failed to set the logger: {0}")]
+  SetLogger(#[from] log::SetLoggerError),
+  /// Invalid store name.
+  #[error("invalid store name `{0}`: it must be a file name without path separators")]
+  InvalidStoreName(String),
   /// API not enabled by Tauri.
   #[error("{0}")]
   ApiNotEnabled(String),
@@ -8,9 +8,13 @@
 
 /// The Tauri-specific settings for your app e.g. notification permission status.
 pub mod settings;
+/// The persistent key-value store API.
+pub mod store;
 
 /// The webview application entry.
 mod app;
+/// The crash reporting API.
+pub mod crash;
 /// The Tauri API endpoints.
 mod endpoints;
 mod error;
@@ -24,7 +28,8 @@ pub use error::Error;
 /// Tauri result type.
 pub type Result<T> = std::result::Result<T, Error>;
 
-pub(crate) mod async_runtime;
+/// The async runtime running the Tauri tasks.
+pub mod async_runtime;
 
 /// A task to run on the main thread.
 pub type SyncTask = Box<dyn FnOnce() + Send>;
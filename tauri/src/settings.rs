@@ -1,13 +1,9 @@
+use crate::store::Store;
 use serde::{Deserialize, Serialize};
-use std::{
-  fs::File,
-  io::Write,
-  path::{Path, PathBuf},
-};
-use tauri_api::{
-  file::read_string,
-  path::{resolve_path, BaseDirectory},
-};
+use serde_json::Value as JsonValue;
+
+/// The name of the store holding the Tauri settings (saved to `.tauri-settings.json`).
+const SETTINGS_STORE_NAME: &str = ".tauri-settings";
 
 /// Tauri Settings.
 #[derive(Default, Deserialize, Serialize)]
@@ -17,34 +13,21 @@ pub struct Settings {
   pub allow_notification: Option<bool>,
 }
 
-/// Gets the path to the settings file
-fn get_settings_path() -> tauri_api::Result<PathBuf> {
-  resolve_path(".tauri-settings.json", Some(BaseDirectory::App))
+/// Gets the store backing the settings.
+fn settings_store() -> crate::Result<Store> {
+  Store::from_name(SETTINGS_STORE_NAME)
 }
 
 /// Write the settings to the file system.
 pub(crate) fn write_settings(settings: Settings) -> crate::Result<()> {
-  let settings_path = get_settings_path()?;
-  let settings_folder = Path::new(&settings_path).parent().unwrap();
-  if !settings_folder.exists() {
-    std::fs::create_dir(settings_folder)?;
+  match serde_json::to_value(settings)? {
+    JsonValue::Object(entries) => settings_store()?.replace(entries),
+    _ => unreachable!("settings always serialize to an object"),
   }
-  File::create(settings_path)
-    .map_err(Into::into)
-    .and_then(|mut f| {
-      f.write_all(serde_json::to_string(&settings)?.as_bytes())
-        .map_err(Into::into)
-    })
 }
 
 /// Reads the settings from the file system.
 pub fn read_settings() -> crate::Result<Settings> {
-  let settings_path = get_settings_path()?;
-  if settings_path.exists() {
-    read_string(settings_path)
-      .and_then(|settings| serde_json::from_str(settings.as_str()).map_err(Into::into))
-      .map_err(Into::into)
-  } else {
-    Ok(Default::default())
-  }
+  let entries = settings_store()?.entries()?;
+  serde_json::from_value(JsonValue::Object(entries)).map_err(Into::into)
 }
@@ -0,0 +1,293 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use tauri_api::{
+  file::{read_string, write_atomic},
+  path::{resolve_path, BaseDirectory},
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf},
+  sync::{Arc, Mutex},
+};
+
+use crate::{ApplicationExt, WebviewManager};
+
+/// The store entries type.
+pub type StoreEntries = Map<String, JsonValue>;
+
+type StoreCache = Arc<Mutex<HashMap<PathBuf, StoreEntries>>>;
+
+/// The event emitted to all windows and to the Rust listeners when a store is changed.
+pub const STORE_CHANGED_EVENT: &str = "tauri://store-changed";
+
+/// Gets the loaded stores map.
+fn cache() -> &'static StoreCache {
+  static CACHE: Lazy<StoreCache> = Lazy::new(Default::default);
+  &CACHE
+}
+
+/// Gets the sender of the store changed events to the app windows, set once the app runs.
+fn change_sender() -> &'static Mutex<Option<UnboundedSender<JsonValue>>> {
+  static SENDER: Lazy<Mutex<Option<UnboundedSender<JsonValue>>>> = Lazy::new(Default::default);
+  &SENDER
+}
+
+/// Emits the store changed events to all windows of the app, in the order the stores were changed.
+pub(crate) fn emit_changes<A: ApplicationExt + 'static>(webview_manager: WebviewManager<A>) {
+  let (tx, mut rx) = unbounded_channel::<JsonValue>();
+  // the events of a previous app are no longer sent
+  *change_sender()
+    .lock()
+    .expect("Failed to lock store change sender: emit_changes()") = Some(tx);
+  crate::async_runtime::spawn(async move {
+    while let Some(event) = rx.recv().await {
+      let _ = webview_manager.emit(STORE_CHANGED_EVENT, Some(event)).await;
+    }
+  });
+}
+
+/// The payload of the store changed event.
+#[derive(Serialize)]
+struct StoreChangedEvent<'a> {
+  /// The store name.
+  store: &'a str,
+  /// The changed key. `None` if the store was cleared.
+  key: Option<&'a str>,
+  /// The new value. `None` if the key was deleted or the store was cleared.
+  value: Option<&'a JsonValue>,
+}
+
+/// A persistent key-value store.
+///
+/// Each store is saved as a JSON object on `${APP_DIR}/${name}.json`.
+/// The file is loaded on first access and rewritten atomically on every change,
+/// which is emitted as the [`STORE_CHANGED_EVENT`] to all windows and to the Rust listeners.
+///
+/// # Example
+/// ```no_run
+/// use tauri::store::Store;
+/// let store = Store::new("preferences").expect("invalid store name");
+/// store.set("theme", "dark").expect("failed to save the store");
+/// assert_eq!(store.get("theme").unwrap(), Some(serde_json::json!("dark")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Store {
+  name: String,
+  path: PathBuf,
+}
+
+impl Store {
+  /// Opens the store with the given name.
+  ///
+  /// The name must be a plain file name without path separators and can't start with a `.`.
+  pub fn new(name: impl Into<String>) -> crate::Result<Self> {
+    let name = name.into();
+    if is_valid_name(&name) {
+      Self::from_name(name)
+    } else {
+      Err(crate::Error::InvalidStoreName(name))
+    }
+  }
+
+  /// Opens the store with the given name, skipping the name validation.
+  pub(crate) fn from_name(name: impl Into<String>) -> crate::Result<Self> {
+    let name = name.into();
+    let path = resolve_path(format!("{}.json", name), Some(BaseDirectory::App))?;
+    Ok(Self { name, path })
+  }
+
+  /// The store name.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// The path to the store file.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// Gets the value associated with the given key.
+  pub fn get(&self, key: impl AsRef<str>) -> crate::Result<Option<JsonValue>> {
+    self.read(|entries| entries.get(key.as_ref()).cloned())
+  }
+
+  /// Sets the value associated with the given key.
+  pub fn set<S: Serialize>(&self, key: impl Into<String>, value: S) -> crate::Result<()> {
+    let key = key.into();
+    let value = serde_json::to_value(value)?;
+    self.update(|entries| {
+      entries.insert(key.clone(), value.clone());
+    })?;
+    self.notify_change(Some(&key), Some(&value));
+    Ok(())
+  }
+
+  /// Deletes the given key from the store.
+  /// Returns whether the key was present or not.
+  pub fn delete(&self, key: impl AsRef<str>) -> crate::Result<bool> {
+    let key = key.as_ref();
+    let deleted = self.update(|entries| entries.remove(key).is_some())?;
+    if deleted {
+      self.notify_change(Some(key), None);
+    }
+    Ok(deleted)
+  }
+
+  /// Gets all the store entries.
+  pub fn entries(&self) -> crate::Result<StoreEntries> {
+    self.read(|entries| entries.clone())
+  }
+
+  /// Removes all entries from the store.
+  pub fn clear(&self) -> crate::Result<()> {
+    self.update(|entries| entries.clear())?;
+    self.notify_change(None, None);
+    Ok(())
+  }
+
+  /// Replaces all entries of the store.
+  pub(crate) fn replace(&self, new_entries: StoreEntries) -> crate::Result<()> {
+    self.update(|entries| *entries = new_entries)
+  }
+
+  /// Emits the store changed event to the Rust listeners and to all windows.
+  fn notify_change(&self, key: Option<&str>, value: Option<&JsonValue>) {
+    let event = StoreChangedEvent {
+      store: &self.name,
+      key,
+      value,
+    };
+    let event = match serde_json::to_value(&event) {
+      Ok(event) => event,
+      Err(_) => return,
+    };
+    crate::event::on_event(
+      STORE_CHANGED_EVENT.to_string(),
+      None,
+      Some(event.to_string()),
+    );
+    if let Some(sender) = change_sender()
+      .lock()
+      .expect("Failed to lock store change sender: notify_change()")
+      .as_ref()
+    {
+      let _ = sender.send(event);
+    }
+  }
+
+  fn read<T, F: FnOnce(&StoreEntries) -> T>(&self, f: F) -> crate::Result<T> {
+    let mut cache = cache().lock().expect("Failed to lock store cache: read()");
+    let entries = load(&mut cache, &self.path)?;
+    Ok(f(entries))
+  }
+
+  fn update<T, F: FnOnce(&mut StoreEntries) -> T>(&self, f: F) -> crate::Result<T> {
+    let mut cache = cache()
+      .lock()
+      .expect("Failed to lock store cache: update()");
+    let entries = load(&mut cache, &self.path)?;
+    let mut new_entries = entries.clone();
+    let result = f(&mut new_entries);
+    if &new_entries != entries {
+      write_atomic(&self.path, serde_json::to_string(&new_entries)?)?;
+      *entries = new_entries;
+    }
+    Ok(result)
+  }
+}
+
+/// Loads the store file into the cache if needed and returns its entries.
+fn load<'a>(
+  cache: &'a mut HashMap<PathBuf, StoreEntries>,
+  path: &Path,
+) -> crate::Result<&'a mut StoreEntries> {
+  if !cache.contains_key(path) {
+    let entries = if path.exists() {
+      serde_json::from_str(&read_string(path)?)?
+    } else {
+      StoreEntries::new()
+    };
+    cache.insert(path.to_path_buf(), entries);
+  }
+  Ok(cache.get_mut(path).expect("store not loaded"))
+}
+
+fn is_valid_name(name: &str) -> bool {
+  !name.is_empty() && !name.starts_with('.') && !name.contains(&['/', '\\', ':'][..])
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn test_store() -> Store {
+    let name = uuid::Uuid::new_v4().to_string();
+    let path = std::env::temp_dir()
+      .join("tauri-store-test")
+      .join(format!("{}.json", name));
+    Store { name, path }
+  }
+
+  #[test]
+  fn check_store_name() {
+    assert!(Store::new("preferences").is_ok());
+    assert!(Store::new("").is_err());
+    assert!(Store::new(".tauri-settings").is_err());
+    assert!(Store::new("../preferences").is_err());
+    assert!(Store::new("nested/preferences").is_err());
+  }
+
+  #[test]
+  fn check_store_operations() {
+    let store = test_store();
+    assert_eq!(store.get("key").unwrap(), None);
+
+    store.set("key", "value").unwrap();
+    store.set("count", 1).unwrap();
+    assert_eq!(
+      store.get("key").unwrap(),
+      Some(JsonValue::String("value".to_string()))
+    );
+    assert_eq!(store.entries().unwrap().len(), 2);
+
+    // the file contents match the store entries
+    let saved: StoreEntries = serde_json::from_str(&read_string(store.path()).unwrap()).unwrap();
+    assert_eq!(saved, store.entries().unwrap());
+
+    assert!(store.delete("key").unwrap());
+    assert!(!store.delete("key").unwrap());
+    assert_eq!(store.get("key").unwrap(), None);
+
+    store.clear().unwrap();
+    assert!(store.entries().unwrap().is_empty());
+
+    std::fs::remove_file(store.path()).unwrap();
+  }
+
+  #[test]
+  fn check_store_changed_event() {
+    let store = test_store();
+    let (tx, rx) = std::sync::mpsc::channel();
+    let name = store.name().to_string();
+    crate::event::listen(STORE_CHANGED_EVENT, None, move |payload| {
+      let payload: JsonValue = serde_json::from_str(&payload.unwrap()).unwrap();
+      if payload["store"] == name.as_str() {
+        tx.send(payload).unwrap();
+      }
+    });
+
+    store.set("key", "value").unwrap();
+    assert!(!store.delete("missing").unwrap());
+    store.clear().unwrap();
+    let events: Vec<JsonValue> = rx.try_iter().collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["key"], "key");
+    assert_eq!(events[0]["value"], "value");
+    assert!(events[1]["key"].is_null());
+
+    std::fs::remove_file(store.path()).unwrap();
+  }
+}